@@ -1,6 +1,53 @@
-use anyhow::Result;
+const YEAR: &'static str = "2022";
+const DAY: &'static str = "01";
 
-fn main() -> Result<()> {
-    println!("from 2022, day 01!");
-    Ok(())
+use aoc::{Conv, FromInput, Input};
+
+#[derive(Debug)]
+struct Inventories {
+    totals: Vec<u64>,
+}
+
+impl FromInput<'_> for Inventories {
+    fn from_input(input: &Input) -> Result<Self, String> {
+        let lines = input.lines();
+        let totals = Conv::sections(lines)
+            .iter()
+            .map(|elf| {
+                elf.iter()
+                    .map(|l| {
+                        l.trim().parse::<u64>().map_err(|e| {
+                            format!(
+                                "Inventories::from_input(): '{}' is not a number! Error: {}",
+                                l, e
+                            )
+                        })
+                    })
+                    .sum::<Result<u64, String>>()
+            })
+            .collect::<Result<Vec<u64>, String>>()?;
+
+        Ok(Self { totals })
+    }
+}
+
+impl Inventories {
+    pub fn top(&self, count: usize) -> Vec<u64> {
+        let mut sorted = self.totals.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        sorted.truncate(count);
+        sorted
+    }
+}
+
+fn part1(inventories: &Inventories) -> String {
+    inventories.top(1).iter().sum::<u64>().to_string()
+}
+
+fn part2(inventories: &Inventories) -> String {
+    inventories.top(3).iter().sum::<u64>().to_string()
+}
+
+fn main() {
+    aoc::run(YEAR, DAY, part1, part2);
 }
@@ -1,99 +1,147 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+
+use aoc::Lines;
 
 const YEAR: &'static str = "2023";
 const DAY: &'static str = "01";
-fn get_transform() -> HashMap<String, String> {
-    HashMap::from([
-        ("1".to_owned(), "1".to_owned()),
-        ("2".to_owned(), "2".to_owned()),
-        ("3".to_owned(), "3".to_owned()),
-        ("4".to_owned(), "4".to_owned()),
-        ("5".to_owned(), "5".to_owned()),
-        ("6".to_owned(), "6".to_owned()),
-        ("7".to_owned(), "7".to_owned()),
-        ("8".to_owned(), "8".to_owned()),
-        ("9".to_owned(), "9".to_owned()),
-        ("one".to_owned(), "1".to_owned()),
-        ("two".to_owned(), "2".to_owned()),
-        ("three".to_owned(), "3".to_owned()),
-        ("four".to_owned(), "4".to_owned()),
-        ("five".to_owned(), "5".to_owned()),
-        ("six".to_owned(), "6".to_owned()),
-        ("seven".to_owned(), "7".to_owned()),
-        ("eight".to_owned(), "8".to_owned()),
-        ("nine".to_owned(), "9".to_owned()),
-    ])
+
+const DIGITS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+// Aho-Corasick automaton over the vocabulary, so every (possibly
+// overlapping) occurrence is found in a single pass over the line.
+#[derive(Debug)]
+struct DigitScanner {
+    transitions: Vec<HashMap<char, usize>>,
+    fail: Vec<usize>,
+    // (word length in chars, digit value) for every word ending at a node
+    outputs: Vec<Vec<(usize, u32)>>,
 }
 
-fn part1(input: &Vec<String>) -> String {
-    let mut numbers: Vec<u16> = vec![];
-    for line in input {
-        let mut res: String = "".to_owned();
-        res.push(line.chars().find(|c| c.is_numeric()).unwrap());
-        res.push(line.chars().rev().find(|c| c.is_numeric()).unwrap());
-        numbers.push(res.parse().unwrap());
+impl DigitScanner {
+    pub fn new(vocabulary: &[(&str, u32)]) -> Self {
+        let mut scanner = Self {
+            transitions: vec![HashMap::new()],
+            fail: vec![0],
+            outputs: vec![vec![]],
+        };
+
+        for (word, value) in vocabulary {
+            let mut node = 0;
+            for ch in word.chars() {
+                node = match scanner.transitions[node].get(&ch) {
+                    Some(next) => *next,
+                    None => {
+                        scanner.transitions.push(HashMap::new());
+                        scanner.fail.push(0);
+                        scanner.outputs.push(vec![]);
+                        let next = scanner.transitions.len() - 1;
+                        scanner.transitions[node].insert(ch, next);
+                        next
+                    }
+                };
+            }
+            scanner.outputs[node].push((word.chars().count(), *value));
+        }
+
+        scanner.link_failures();
+        scanner
     }
-    numbers.iter().sum::<u16>().to_string()
-}
 
-fn part2(input: &Vec<String>) -> String {
-    let mut numbers: Vec<u32> = vec![];
-    let mut first_key_index: Option<usize> = None;
-    let mut last_key_index: Option<usize> = None;
-    let mut first_key = "";
-    let mut last_key = "";
-    let tf = get_transform();
-
-    for line in input {
-        for key in tf.keys() {
-            if let Some(cur_index) = line.find(key) {
-                if first_key_index.is_none() || cur_index < first_key_index.unwrap() {
-                    first_key_index = Some(cur_index);
-                    first_key = key;
+    fn link_failures(&mut self) {
+        let mut queue: VecDeque<usize> = self.transitions[0].values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let edges: Vec<(char, usize)> = self.transitions[node]
+                .iter()
+                .map(|(c, n)| (*c, *n))
+                .collect();
+            for (ch, child) in edges {
+                let mut fallback = self.fail[node];
+                while fallback != 0 && !self.transitions[fallback].contains_key(&ch) {
+                    fallback = self.fail[fallback];
                 }
+                self.fail[child] = match self.transitions[fallback].get(&ch) {
+                    Some(target) if *target != child => *target,
+                    _ => 0,
+                };
+
+                let inherited = self.outputs[self.fail[child]].clone();
+                self.outputs[child].extend(inherited);
+                queue.push_back(child);
             }
-            if let Some(cur_index) = line.rfind(key) {
-                if last_key_index.is_none() || cur_index >= last_key_index.unwrap() {
-                    last_key_index = Some(cur_index);
-                    last_key = key;
-                }
+        }
+    }
+
+    // Returns (start char index, value) for every match, overlaps included.
+    pub fn scan(&self, line: &str) -> Vec<(usize, u32)> {
+        let mut matches: Vec<(usize, u32)> = vec![];
+        let mut node = 0;
+        for (index, ch) in line.chars().enumerate() {
+            while node != 0 && !self.transitions[node].contains_key(&ch) {
+                node = self.fail[node];
+            }
+            node = self.transitions[node].get(&ch).copied().unwrap_or(0);
+            for (len, value) in &self.outputs[node] {
+                matches.push((index + 1 - len, *value));
             }
         }
-        numbers.push(
-            format!(
-                "{}{}",
-                tf.get(first_key).unwrap(),
-                tf.get(last_key).unwrap(),
-            )
-            .parse::<u32>()
-            .unwrap(),
-        );
-        first_key_index = None;
-        last_key_index = None;
-        first_key = "";
-        last_key = "";
+
+        matches
+    }
+
+    pub fn calibration(&self, line: &str) -> Result<u32, String> {
+        let matches = self.scan(line);
+        let first = matches.iter().min_by_key(|(start, _)| *start);
+        let last = matches.iter().max_by_key(|(start, _)| *start);
+        match (first, last) {
+            (Some((_, first)), Some((_, last))) => Ok(first * 10 + last),
+            _ => Err(format!(
+                "DigitScanner::calibration(): no digits found in '{}'!",
+                line
+            )),
+        }
     }
-    numbers.iter().sum::<u32>().to_string()
+}
+
+fn calibration_sum(lines: &[&str], scanner: &DigitScanner) -> Result<u32, String> {
+    lines.iter().map(|line| scanner.calibration(line)).sum()
+}
+
+fn part1(lines: &[&str]) -> Result<u32, String> {
+    calibration_sum(lines, &DigitScanner::new(&DIGITS))
+}
+
+fn part2(lines: &[&str]) -> Result<u32, String> {
+    let vocabulary: Vec<(&str, u32)> = DIGITS.iter().chain(WORDS.iter()).copied().collect();
+    calibration_sum(lines, &DigitScanner::new(&vocabulary))
 }
 
 fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
-
-    let sample_res = match &inputs.sample {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+    aoc::run(
+        YEAR,
+        DAY,
+        |lines: &Lines| part1(lines),
+        |lines: &Lines| part2(lines),
+    );
 }
@@ -1,157 +1,153 @@
-use std::collections::HashMap;
-
-use regex::Regex;
-
 const YEAR: &'static str = "2023";
 const DAY: &'static str = "02";
 
-#[derive(Debug)]
+const BAG_CAPACITY: &'static str = "12 red, 13 green, 14 blue";
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+
+// Colours with a count of zero are never stored, so the derived equality
+// agrees with the element-wise ordering below.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 struct CubeSet {
-    pub red: u16,
-    pub green: u16,
-    pub blue: u16,
+    counts: BTreeMap<String, u32>,
 }
 
 impl CubeSet {
-    pub fn zeroed() -> Self {
-        Self {
-            red: 0,
-            green: 0,
-            blue: 0,
+    pub fn get(&self, color: &str) -> u32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn insert(&mut self, color: &str, count: u32) {
+        if count == 0 {
+            return;
+        }
+        *self.counts.entry(color.to_string()).or_insert(0) += count;
+    }
+
+    pub fn maximum(&self, other: &CubeSet) -> CubeSet {
+        let mut result = self.clone();
+        for (color, count) in &other.counts {
+            let entry = result.counts.entry(color.clone()).or_insert(0);
+            *entry = (*entry).max(*count);
         }
+
+        result
+    }
+
+    // Product over every colour in `universe` (and in this set), so a colour
+    // the set never shows counts as 0.
+    pub fn power(&self, universe: &CubeSet) -> u64 {
+        self.colors(universe)
+            .into_iter()
+            .map(|color| self.get(color) as u64)
+            .product()
+    }
+
+    fn colors<'a>(&'a self, other: &'a CubeSet) -> BTreeSet<&'a str> {
+        self.counts
+            .keys()
+            .chain(other.counts.keys())
+            .map(|c| c.as_str())
+            .collect()
     }
+}
+
+// Element-wise comparison: a set is <= another if no color exceeds it.
+impl PartialOrd for CubeSet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let (mut less, mut greater) = (false, false);
+        for color in self.colors(other) {
+            match self.get(color).cmp(&other.get(color)) {
+                Ordering::Less => less = true,
+                Ordering::Greater => greater = true,
+                Ordering::Equal => {}
+            }
+        }
 
-    pub fn power(&self) -> u32 {
-        (self.red * self.green * self.blue) as u32
+        match (less, greater) {
+            (false, false) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (true, true) => None,
+        }
+    }
+}
+
+impl FromStr for CubeSet {
+    type Err = String;
+
+    fn from_str(set: &str) -> Result<Self, Self::Err> {
+        let mut cube_set = CubeSet::default();
+        for cubes in set.split(',') {
+            let (count, color) = aoc::parse!("{} {}", cubes.trim() => u32, String)?;
+            cube_set.insert(&color, count);
+        }
+
+        Ok(cube_set)
     }
 }
 
 #[derive(Debug)]
 struct Game {
-    pub id: u16,
+    pub id: u32,
     pub sets: Vec<CubeSet>,
-    pub capacity: CubeSet,
 }
 
 impl Game {
-    pub fn new(id: u16) -> Self {
-        Self {
-            id,
-            sets: vec![],
-            capacity: CubeSet {
-                red: 12,
-                green: 13,
-                blue: 14,
-            },
-        }
-    }
-
-    pub fn is_possible(&self) -> bool {
-        for set in &self.sets {
-            if set.red > self.capacity.red
-                || set.green > self.capacity.green
-                || set.blue > self.capacity.blue
-            {
-                return false;
-            }
-        }
-        true
+    pub fn is_possible(&self, capacity: &CubeSet) -> bool {
+        self.sets.iter().all(|set| set <= capacity)
     }
 
     pub fn minimum_set(&self) -> CubeSet {
-        let (reds, greens, blues) = (
-            self.sets.iter().map(|s| s.red),
-            self.sets.iter().map(|s| s.green),
-            self.sets.iter().map(|s| s.blue),
-        );
-
-        CubeSet {
-            red: reds.max().unwrap_or(0),
-            green: greens.max().unwrap_or(0),
-            blue: blues.max().unwrap_or(0),
-        }
+        self.sets
+            .iter()
+            .fold(CubeSet::default(), |acc, set| acc.maximum(set))
     }
 }
 
-fn input_to_games(input: &Vec<String>) -> Vec<Game> {
-    let mut games: Vec<Game> = vec![];
-    for line in input {
-        let ided = line
-            .split(':')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
-        let id_re = Regex::new(r"^Game\s+(\d+)$").unwrap();
-        let id_caps = id_re.captures(&ided[0]).unwrap();
-        let mut game = Game::new(id_caps[1].parse::<u16>().unwrap());
-        let sets = ided[1].trim().split(';');
-
-        for set in sets {
-            let set_re = Regex::new(r"(\d+)\s+(red|green|blue)").unwrap();
-            let mut colors: HashMap<String, u16> = HashMap::new();
-            for cap in set_re.captures_iter(set) {
-                let count = cap[1].to_string().parse::<u16>().unwrap();
-                colors.insert(cap[2].to_string(), count);
-            }
-            let mut cube_set = CubeSet::zeroed();
-            if colors.contains_key("red") {
-                cube_set.red = *colors.get("red").unwrap();
-            }
-            if colors.contains_key("green") {
-                cube_set.green = *colors.get("green").unwrap();
-            }
-            if colors.contains_key("blue") {
-                cube_set.blue = *colors.get("blue").unwrap();
-            }
-            game.sets.push(cube_set);
-        }
-        games.push(game);
-    }
+impl FromStr for Game {
+    type Err = String;
 
-    games
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (id, sets) = aoc::parse!("Game {}: {}", line => u32, String)?;
+        Ok(Game {
+            id,
+            sets: sets
+                .split(';')
+                .map(CubeSet::from_str)
+                .collect::<Result<_, _>>()?,
+        })
+    }
 }
 
-fn part1(games: &Vec<Game>) -> String {
+fn part1(games: &[Game], capacity: &CubeSet) -> String {
     games
         .iter()
-        .filter(|g| g.is_possible())
+        .filter(|g| g.is_possible(capacity))
         .map(|g| g.id)
-        .sum::<u16>()
+        .sum::<u32>()
         .to_string()
 }
 
-fn part2(games: &Vec<Game>) -> String {
+fn part2(games: &[Game], colors: &CubeSet) -> String {
     games
         .iter()
         .map(|g| g.minimum_set())
-        .map(|gs| gs.power())
-        .sum::<u32>()
+        .map(|gs| gs.power(colors))
+        .sum::<u64>()
         .to_string()
 }
 
 fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
-
-    let sample_res = match &inputs.sample {
-        Some(input) => {
-            let games = input_to_games(input);
-            aoc::DayResults::new(part1(&games), part2(&games))
-        }
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => {
-            let games = input_to_games(input);
-            aoc::DayResults::new(part1(&games), part2(&games))
-        }
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+    let capacity = CubeSet::from_str(BAG_CAPACITY).expect("main(): invalid bag capacity!");
+    aoc::run(
+        YEAR,
+        DAY,
+        |games: &Vec<Game>| part1(games, &capacity),
+        |games: &Vec<Game>| part2(games, &capacity),
+    );
 }
@@ -1,9 +1,12 @@
 const YEAR: &'static str = "2023";
 const DAY: &'static str = "04";
 
-use regex::Regex;
+use aoc::Conv;
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{BTreeMap, HashSet},
+    str::FromStr,
+};
 
 #[derive(Debug)]
 struct Card {
@@ -12,6 +15,19 @@ struct Card {
     pub mine: Vec<u8>,
 }
 
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (id, winning, mine) = aoc::parse!("Card {}: {} | {}", line => usize, String, String)?;
+        Ok(Card {
+            id,
+            winning: Conv::numbers_ws(&winning)?,
+            mine: Conv::numbers_ws(&mine)?,
+        })
+    }
+}
+
 impl Card {
     pub fn winning_numbers(&self) -> Vec<u8> {
         let winning_set: HashSet<_> = self.winning.clone().into_iter().collect();
@@ -23,101 +39,119 @@ impl Card {
             .collect::<Vec<u8>>()
     }
 
-    pub fn points(&self) -> u32 {
-        2_u32.pow(self.winning_numbers().len() as u32 - 1)
+    pub fn points(&self) -> Result<u64, String> {
+        match self.winning_numbers().len() {
+            0 => Ok(0),
+            matches => 1u64.checked_shl(matches as u32 - 1).ok_or_else(|| {
+                format!(
+                    "Card::points(): card {} has {} matches, its points overflow u64!",
+                    self.id, matches
+                )
+            }),
+        }
     }
 }
 
-fn part1(cards: &Vec<Card>) -> String {
-    cards.iter().map(|c| c.points()).sum::<u32>().to_string()
+fn part1(cards: &[Card]) -> Result<u64, String> {
+    cards.iter().try_fold(0u64, |total, card| {
+        total
+            .checked_add(card.points()?)
+            .ok_or_else(|| "part1(): the points total overflows u64!".to_string())
+    })
 }
 
-fn part2(cards: &Vec<Card>) -> String {
-    let mut card_counts: HashMap<usize, usize> = cards.iter().map(|c| (c.id, 1)).collect();
-
-    for card in cards {
-        // the overall result for actual input is for some reason incorrect
-        // this is verified to be correct up until card 20 (inclusive)
-        // (correct for sample input)
-        let i_won_x_cards = card.winning_numbers().len();
-        let next_copies = card.id + i_won_x_cards;
-        let upper_limit = if next_copies < cards.len() {
-            next_copies
-        } else {
-            cards.len() - 1
-        };
-        for copy_id in card.id + 1..=upper_limit {
-            card_counts.insert(
-                copy_id,
-                card_counts.get(&copy_id).unwrap() + card_counts.get(&card.id).unwrap(),
-            );
+fn part2(cards: &[Card]) -> String {
+    // Copies only ever go to higher ids, so by the time a card is reached
+    // in ascending id order its count is final.
+    let mut card_counts: BTreeMap<usize, u64> = cards.iter().map(|c| (c.id, 1)).collect();
+    let mut by_id: Vec<&Card> = cards.iter().collect();
+    by_id.sort_by_key(|c| c.id);
+
+    for card in by_id {
+        let copies = card_counts[&card.id];
+        let won = card.winning_numbers().len();
+        for copy_id in card.id + 1..=card.id + won {
+            if let Some(count) = card_counts.get_mut(&copy_id) {
+                *count += copies;
+            }
         }
     }
 
-    card_counts.values().sum::<usize>().to_string()
+    card_counts.values().sum::<u64>().to_string()
 }
 
-fn convert_nums(nums_str: &str) -> Vec<u8> {
-    nums_str
-        .split(' ')
-        .filter(|s| !s.is_empty())
-        .map(|s| s.parse::<u8>().unwrap())
-        .collect()
+fn main() {
+    aoc::run(
+        YEAR,
+        DAY,
+        |cards: &Vec<Card>| part1(cards),
+        |cards: &Vec<Card>| part2(cards),
+    );
 }
 
-fn lines_to_cards(lines: &Vec<String>) -> Vec<Card> {
-    let mut cards: Vec<Card> = vec![];
-    for line in lines {
-        let mut id: usize = 0;
-        let id_split = line
-            .split(':')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
-        let id_re = Regex::new(r"Card\s+(\d+)").unwrap();
-        id = id_re.captures(id_split.get(0).unwrap()).unwrap()[1]
-            .parse::<usize>()
-            .unwrap();
-
-        let numbers_split = id_split
-            .get(1)
-            .unwrap()
-            .split('|')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
-        cards.push(Card {
-            id,
-            winning: convert_nums(numbers_split.get(0).unwrap()),
-            mine: convert_nums(numbers_split.get(1).unwrap()),
-        });
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(lines: &[&str]) -> Vec<Card> {
+        lines.iter().map(|l| l.parse().unwrap()).collect()
     }
 
-    cards
-}
+    const SAMPLE: [&str; 6] = [
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+        "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+        "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+        "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+        "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+        "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+    ];
+
+    #[test]
+    fn sample() {
+        let cards = cards(&SAMPLE);
+        assert_eq!(part1(&cards), Ok(13));
+        assert_eq!(part2(&cards), "30");
+    }
 
-fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
+    #[test]
+    fn zero_matches_score_nothing() {
+        let cards = cards(&["Card 1: 1 2 | 3 4", "Card 2: 5 | 6"]);
+        assert_eq!(cards[0].points(), Ok(0));
+        assert_eq!(part1(&cards), Ok(0));
+        assert_eq!(part2(&cards), "2");
+    }
 
-    let sample_res = match &inputs.sample {
-        Some(input) => {
-            let cards = lines_to_cards(input);
-            aoc::DayResults::new(part1(&cards), part2(&cards))
-        }
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => {
-            let cards = lines_to_cards(input);
-            aoc::DayResults::new(part1(&cards), part2(&cards))
-        }
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+    #[test]
+    fn last_card_winning_copies_nothing() {
+        let cards = cards(&["Card 1: 1 | 2", "Card 2: 1 2 3 | 1 2 3"]);
+        assert_eq!(part1(&cards), Ok(4));
+        assert_eq!(part2(&cards), "2");
+    }
+
+    #[test]
+    fn wins_are_clamped_to_the_table_end() {
+        let cards = cards(&["Card 1: 1 2 3 | 1 2 3", "Card 2: 1 | 1", "Card 3: 1 | 2"]);
+        // card 1 copies 2 and 3, then both instances of card 2 copy card 3
+        assert_eq!(part2(&cards), "7");
+    }
+
+    #[test]
+    fn cards_are_matched_by_id_not_position() {
+        let cards = cards(&["Card 2: 1 | 2", "Card 1: 1 | 1"]);
+        assert_eq!(part2(&cards), "3");
+    }
+
+    #[test]
+    fn points_overflow_is_an_error() {
+        let numbers: Vec<String> = (0..64).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let card = format!("Card 1: {} | {}", numbers, numbers);
+        let largest = cards(&[&card]);
+        assert_eq!(largest[0].points(), Ok(1 << 63));
+
+        let card = format!("Card 1: {} 64 | {} 64", numbers, numbers);
+        let overflowing = cards(&[&card]);
+        assert!(overflowing[0].points().is_err());
+        assert!(part1(&overflowing).is_err());
+    }
 }
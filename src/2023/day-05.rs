@@ -1,38 +1,157 @@
 const YEAR: &'static str = "2023";
 const DAY: &'static str = "05";
 
-fn part1(_cards: &Vec<String>) -> String {
-    "1".to_string()
+use aoc::{Conv, FromInput, Input, IntervalSet, RangeMapping};
+
+fn parse_mapping(line: &str) -> Result<RangeMapping, String> {
+    match Conv::numbers_ws::<i64>(line)?.as_slice() {
+        [dest, src, len] => Ok(RangeMapping::new(*src..src + len, dest - src)),
+        _ => Err(format!(
+            "parse_mapping(): range line '{}' must consist of three numbers!",
+            line
+        )),
+    }
 }
 
-fn part2(_cards: &Vec<String>) -> String {
-    "2".to_string()
+#[derive(Debug)]
+struct AlmanacMap {
+    from: String,
+    to: String,
+    mappings: Vec<RangeMapping>,
 }
 
-fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
+impl AlmanacMap {
+    pub fn new(section: &[&str]) -> Result<Self, String> {
+        let (header, ranges) = section
+            .split_first()
+            .ok_or("AlmanacMap::new(): the map section is empty!")?;
+        let (from, to) = header
+            .trim()
+            .strip_suffix(" map:")
+            .and_then(|h| h.split_once("-to-"))
+            .ok_or_else(|| {
+                format!(
+                    "AlmanacMap::new(): header '{}' must look like 'x-to-y map:'!",
+                    header
+                )
+            })?;
+
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            mappings: ranges
+                .iter()
+                .map(|l| parse_mapping(l))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn lookup(&self, value: i64) -> i64 {
+        self.mappings
+            .iter()
+            .find_map(|m| m.apply(value))
+            .unwrap_or(value)
+    }
+}
+
+#[derive(Debug)]
+struct Almanac {
+    seeds: Vec<i64>,
+    // the same seed numbers read as (start, length) pairs
+    seed_ranges: IntervalSet,
+    maps: Vec<AlmanacMap>,
+}
+
+impl FromInput<'_> for Almanac {
+    fn from_input(input: &Input) -> Result<Self, String> {
+        let sections = Conv::sections(input.lines());
+        let (seed_section, map_sections) = sections
+            .split_first()
+            .ok_or("Almanac::from_input(): the almanac is empty!")?;
+        let seeds = seed_section
+            .first()
+            .and_then(|l| l.strip_prefix("seeds:"))
+            .ok_or("Almanac::from_input(): the first section must list the seeds!")?;
+        let seeds = Conv::numbers_ws::<i64>(seeds)?;
+        let seed_ranges = Self::pair_seeds(&seeds)?;
+        let maps = map_sections
+            .iter()
+            .map(|m| AlmanacMap::new(m))
+            .collect::<Result<Vec<AlmanacMap>, String>>()?;
+
+        if maps.windows(2).any(|pair| pair[0].to != pair[1].from) {
+            return Err("Almanac::from_input(): the maps do not form a single chain!".to_string());
+        }
+
+        Ok(Self {
+            seeds,
+            seed_ranges,
+            maps,
+        })
+    }
+}
+
+impl Almanac {
+    pub fn location(&self, seed: i64) -> i64 {
+        self.maps.iter().fold(seed, |value, map| map.lookup(value))
+    }
+
+    pub fn location_ranges(&self) -> IntervalSet {
+        self.maps
+            .iter()
+            .fold(self.seed_ranges.clone(), |ranges, map| {
+                ranges.map_through(&map.mappings)
+            })
+    }
 
-    let sample_res = match &inputs.sample {
-        Some(input) => {
-            // let cards = lines_to_cards(input);
-            aoc::DayResults::new(part1(input), part2(input))
+    fn pair_seeds(seeds: &[i64]) -> Result<IntervalSet, String> {
+        if seeds.is_empty() {
+            return Err("Almanac::pair_seeds(): the almanac lists no seeds!".to_string());
         }
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => {
-            // let cards = lines_to_cards(input);
-            aoc::DayResults::new(part1(input), part2(input))
+        if !seeds.len().is_multiple_of(2) {
+            return Err(format!(
+                "Almanac::pair_seeds(): seeds must come in (start, length) pairs, found {} numbers!",
+                seeds.len()
+            ));
         }
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+
+        seeds
+            .chunks(2)
+            .map(|pair| match pair {
+                [start, len] if *len >= 0 => start
+                    .checked_add(*len)
+                    .map(|end| *start..end)
+                    .ok_or_else(|| {
+                        format!(
+                            "Almanac::pair_seeds(): seed range {} + {} overflows!",
+                            start, len
+                        )
+                    }),
+                _ => Err(format!(
+                    "Almanac::pair_seeds(): seed range {:?} has a negative length!",
+                    pair
+                )),
+            })
+            .collect()
+    }
+}
+
+fn part1(almanac: &Almanac) -> Result<i64, String> {
+    almanac
+        .seeds
+        .iter()
+        .map(|seed| almanac.location(*seed))
+        .min()
+        .ok_or("part1(): there are no seeds to locate!".to_string())
+}
+
+fn part2(almanac: &Almanac) -> Result<i64, String> {
+    almanac
+        .location_ranges()
+        .min()
+        .ok_or("part2(): the seed ranges are all empty!".to_string())
+}
+
+fn main() {
+    aoc::run(YEAR, DAY, part1, part2);
 }
@@ -1,11 +1,9 @@
 const YEAR: &'static str = "2024";
 const DAY: &'static str = "02";
 
-use std::cmp::Ordering;
+use aoc::{Conv, FromInput, Input};
 
-use aoc::Conv;
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct Level {
     value: u32,
 }
@@ -13,6 +11,16 @@ impl Level {
     pub fn new(value: u32) -> Self {
         Level { value }
     }
+
+    fn steps_to(&self, next: &Level, increasing: bool) -> bool {
+        let step_ok = if increasing {
+            self.value < next.value
+        } else {
+            self.value > next.value
+        };
+
+        step_ok && self.value.abs_diff(next.value) <= 3
+    }
 }
 
 #[derive(Debug)]
@@ -20,55 +28,77 @@ pub struct Reports {
     reports: Vec<Vec<Level>>,
 }
 
-impl Reports {
-    pub fn new(lines: &Vec<String>) -> Self {
-        Self {
-            reports: Self::pack(Conv::to_matrix(lines)),
-        }
+impl FromInput<'_> for Reports {
+    fn from_input(input: &Input) -> Result<Self, String> {
+        let lines = input.lines();
+        Ok(Self {
+            reports: Self::pack(Conv::numbers_matrix(lines)?),
+        })
     }
+}
 
-    pub fn check_safety(&self) -> Vec<u8> {
-        let (inc_or_dec, within_range) = self.base_conditions();
-
-        inc_or_dec
+impl Reports {
+    pub fn count_safe(&self, tolerance: usize) -> usize {
+        self.reports
             .iter()
-            .zip(within_range)
-            .map(|(id, wr)| (*id && wr) as u8)
-            .collect::<Vec<_>>()
+            .filter(|report| Self::dampened_safety(report, tolerance).is_some())
+            .count()
     }
 
-    pub fn safety_dampened(&self) -> Vec<u8> {
-        self.reports
-            .iter()
-            .enumerate()
-            .map(|(_, report)| {
-                if Self::is_safe(report) {
-                    return 1;
-                }
+    // Returns the indices of the fewest levels (at most `tolerance`) whose
+    // removal makes the report safe, or None if no such set exists.
+    pub fn dampened_safety(report: &[Level], tolerance: usize) -> Option<Vec<usize>> {
+        [true, false]
+            .into_iter()
+            .filter_map(|increasing| Self::removals(report, tolerance, increasing))
+            .min_by_key(|removed| removed.len())
+    }
 
-                for i in 0..report.len() {
-                    let mut modified = report.clone();
-                    modified.remove(i);
+    // DP over kept levels: reach[i][r] is Some(previous kept level) when a
+    // monotonic run can end at level i with r levels removed so far. Only the
+    // `tolerance + 1` preceding levels can feed level i, so this is O(n * k^2).
+    fn removals(report: &[Level], tolerance: usize, increasing: bool) -> Option<Vec<usize>> {
+        let len = report.len();
+        if len == 0 {
+            return Some(vec![]);
+        }
 
-                    if Self::is_safe(&modified) {
-                        return 1;
+        let mut reach: Vec<Vec<Option<Option<usize>>>> = vec![vec![None; tolerance + 1]; len];
+        for i in 0..len {
+            if i <= tolerance {
+                reach[i][i] = Some(None);
+            }
+            for j in i.saturating_sub(tolerance + 1)..i {
+                if !report[j].steps_to(&report[i], increasing) {
+                    continue;
+                }
+                let skipped = i - j - 1;
+                for r in 0..=tolerance - skipped {
+                    if reach[j][r].is_some() && reach[i][r + skipped].is_none() {
+                        reach[i][r + skipped] = Some(Some(j));
                     }
                 }
+            }
+        }
 
-                0
-            })
-            .collect()
-    }
-
-    fn is_safe(report: &Vec<Level>) -> bool {
-        let is_increasing = report.windows(2).all(|pair| pair[0].value < pair[1].value);
-        let is_decreasing = report.windows(2).all(|pair| pair[0].value > pair[1].value);
-        let within_range = report
-            .windows(2)
-            .all(|pair| pair[0].value.abs_diff(pair[1].value) <= 3);
-        let inc_or_dec = is_increasing || is_decreasing;
+        let (mut last, mut removed) = (len.saturating_sub(tolerance + 1)..len)
+            .flat_map(|i| (0..=tolerance).map(move |r| (i, r)))
+            .filter(|(i, r)| r + len - 1 - i <= tolerance && reach[*i][*r].is_some())
+            .min_by_key(|(i, r)| r + len - 1 - i)?;
+
+        let mut kept = vec![false; len];
+        loop {
+            kept[last] = true;
+            match reach[last][removed] {
+                Some(Some(previous)) => {
+                    removed -= last - previous - 1;
+                    last = previous;
+                }
+                _ => break,
+            }
+        }
 
-        inc_or_dec && within_range
+        Some((0..len).filter(|i| !kept[*i]).collect())
     }
 
     fn pack(matrix: Vec<Vec<u32>>) -> Vec<Vec<Level>> {
@@ -77,88 +107,125 @@ impl Reports {
             .map(|row| row.iter().map(|val| Level::new(*val)).collect())
             .collect::<Vec<_>>()
     }
-
-    fn slices(&self, size: usize) -> Vec<Vec<&[Level]>> {
-        self.reports
-            .iter()
-            .map(|report| report.windows(size).collect())
-            .collect::<Vec<Vec<&[Level]>>>()
-    }
-
-    fn base_conditions(&self) -> (Vec<bool>, Vec<bool>) {
-        let pairs = self.slices(2);
-        let inc = pairs
-            .iter()
-            .map(|pairvec| {
-                pairvec
-                    .iter()
-                    .map(|pair| pair[0].value.cmp(&pair[1].value))
-                    .all(|o| o == Ordering::Greater)
-            })
-            .collect::<Vec<_>>();
-        let dec = pairs
-            .iter()
-            .map(|pairvec| {
-                pairvec
-                    .iter()
-                    .map(|pair| pair[0].value.cmp(&pair[1].value))
-                    .all(|o| o == Ordering::Less)
-            })
-            .collect::<Vec<_>>();
-        let inc_or_dec = inc
-            .iter()
-            .zip(dec)
-            .map(|(i, d)| *i || d)
-            .collect::<Vec<_>>();
-        let within_range = pairs
-            .iter()
-            .map(|pairvec| {
-                pairvec
-                    .iter()
-                    .all(|pair| pair[0].value.abs_diff(pair[1].value) <= 3)
-            })
-            .collect::<Vec<_>>();
-
-        (inc_or_dec, within_range)
-    }
 }
 
-fn part1(lists: &Vec<String>) -> String {
-    Reports::new(lists)
-        .check_safety()
-        .iter()
-        .map(|ue| *ue as u64)
-        .sum::<u64>()
-        .to_string()
+fn part1(reports: &Reports) -> String {
+    reports.count_safe(0).to_string()
 }
 
-fn part2(lists: &Vec<String>) -> String {
-    Reports::new(lists)
-        .safety_dampened()
-        .iter()
-        .map(|ue| *ue as u64)
-        .sum::<u64>()
-        .to_string()
+fn part2(reports: &Reports) -> String {
+    reports.count_safe(1).to_string()
 }
 
 fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
-
-    let sample_res = match &inputs.sample {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+    aoc::run(YEAR, DAY, part1, part2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(values: &[u32]) -> Vec<Level> {
+        values.iter().map(|v| Level::new(*v)).collect()
+    }
+
+    fn is_safe(values: &[u32]) -> bool {
+        let increasing = values.windows(2).all(|p| p[0] < p[1]);
+        let decreasing = values.windows(2).all(|p| p[0] > p[1]);
+        let within_range = values.windows(2).all(|p| p[0].abs_diff(p[1]) <= 3);
+        (increasing || decreasing) && within_range
+    }
+
+    // The original approach: try the report as is, then without each level.
+    fn brute_force(values: &[u32], tolerance: usize) -> bool {
+        is_safe(values)
+            || (tolerance >= 1
+                && (0..values.len()).any(|skip| {
+                    let mut removed = values.to_vec();
+                    removed.remove(skip);
+                    is_safe(&removed)
+                }))
+    }
+
+    fn without(values: &[u32], removed: &[usize]) -> Vec<u32> {
+        (0..values.len())
+            .filter(|i| !removed.contains(i))
+            .map(|i| values[i])
+            .collect()
+    }
+
+    #[test]
+    fn sample() {
+        let reports = Reports {
+            reports: Reports::pack(vec![
+                vec![7, 6, 4, 2, 1],
+                vec![1, 2, 7, 8, 9],
+                vec![9, 7, 6, 2, 1],
+                vec![1, 3, 2, 4, 5],
+                vec![8, 6, 4, 4, 1],
+                vec![1, 3, 6, 7, 9],
+            ]),
+        };
+        assert_eq!(part1(&reports), "2");
+        assert_eq!(part2(&reports), "4");
+    }
+
+    #[test]
+    fn safe_reports_need_no_removals() {
+        assert_eq!(
+            Reports::dampened_safety(&levels(&[1, 2, 4, 7]), 0),
+            Some(vec![])
+        );
+        assert_eq!(
+            Reports::dampened_safety(&levels(&[7, 4, 2, 1]), 1),
+            Some(vec![])
+        );
+        assert_eq!(Reports::dampened_safety(&levels(&[]), 0), Some(vec![]));
+        assert_eq!(Reports::dampened_safety(&levels(&[5]), 0), Some(vec![]));
+    }
+
+    #[test]
+    fn edge_levels_can_be_removed() {
+        assert_eq!(
+            Reports::dampened_safety(&levels(&[9, 1, 2, 3]), 1),
+            Some(vec![0])
+        );
+        assert_eq!(
+            Reports::dampened_safety(&levels(&[1, 2, 3, 9]), 1),
+            Some(vec![3])
+        );
+        assert_eq!(
+            Reports::dampened_safety(&levels(&[1, 2, 9, 3]), 1),
+            Some(vec![2])
+        );
+        assert_eq!(Reports::dampened_safety(&levels(&[9, 1, 2, 3]), 0), None);
+        assert_eq!(Reports::dampened_safety(&levels(&[1, 9, 9, 2]), 1), None);
+    }
+
+    #[test]
+    fn matches_brute_force_on_every_small_report() {
+        for len in 0..=6 {
+            for code in 0..6_usize.pow(len) {
+                let values: Vec<u32> = (0..len)
+                    .map(|i| (code / 6_usize.pow(i) % 6) as u32 * 2)
+                    .collect();
+                for tolerance in 0..=1 {
+                    let result = Reports::dampened_safety(&levels(&values), tolerance);
+                    assert_eq!(
+                        result.is_some(),
+                        brute_force(&values, tolerance),
+                        "{:?} with tolerance {}",
+                        values,
+                        tolerance
+                    );
+
+                    if let Some(removed) = result {
+                        assert!(removed.len() <= tolerance);
+                        assert!(is_safe(&without(&values, &removed)));
+                        assert_eq!(removed.is_empty(), is_safe(&values));
+                    }
+                }
+            }
+        }
+    }
 }
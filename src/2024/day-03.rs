@@ -1,192 +1,216 @@
 const YEAR: &'static str = "2024";
 const DAY: &'static str = "03";
 
-use std::u32;
+use std::env;
 
-use aoc::Conv;
-use regex::Regex;
+use aoc::{FromInput, Input};
 
-#[derive(Debug)]
-pub enum CommandType {
-    Mul(u32, u32),
-    Do,
-    Dont,
-}
+type Handler = fn(&mut Machine, &[u64]) -> Result<String, String>;
 
-#[derive(Debug)]
-struct Capsule {
-    pub cmd: String,
-    pub op1: String,
-    pub op2: String,
+#[derive(Debug, Clone, Copy)]
+struct Opcode {
+    name: &'static str,
+    arity: usize,
+    exec: Handler,
 }
-impl Default for Capsule {
-    fn default() -> Self {
-        Self {
-            cmd: "".to_owned(),
-            op1: "".to_owned(),
-            op2: "".to_owned(),
-        }
-    }
+
+const MUL: Opcode = Opcode {
+    name: "mul",
+    arity: 2,
+    exec: Machine::mul,
+};
+const DO: Opcode = Opcode {
+    name: "do",
+    arity: 0,
+    exec: Machine::enable,
+};
+const DONT: Opcode = Opcode {
+    name: "don't",
+    arity: 0,
+    exec: Machine::disable,
+};
+
+#[derive(Debug, Clone)]
+struct Instruction {
+    opcode: Opcode,
+    args: Vec<u64>,
+    offset: usize,
 }
-impl Capsule {
-    pub fn to_command(&self) -> CommandType {
-        match self.cmd.as_str() {
-            "do" => CommandType::Do,
-            "dont" => CommandType::Dont,
-            "mul" => CommandType::Mul(
-                self.op1.parse::<u32>().unwrap_or(u32::MAX),
-                self.op2.parse::<u32>().unwrap_or(u32::MAX),
-            ),
-            _ => unreachable!("Capsule::to_command(): unexpected command type!"),
-        }
+
+impl Instruction {
+    pub fn text(&self) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{}({})", self.opcode.name, args)
     }
 }
 
 #[derive(Debug)]
-struct Memory {
-    data: String,
-    commands: Vec<CommandType>,
-    filtered_muls: Vec<CommandType>,
+struct Machine {
     enabled: bool,
+    acc: u64,
+    trace: Option<Vec<String>>,
 }
 
-impl Memory {
-    pub fn new(lines: &Vec<String>) -> Self {
+impl Machine {
+    pub fn new(trace: bool) -> Self {
         Self {
-            data: Conv::to_string(lines),
-            commands: vec![],
-            filtered_muls: vec![],
             enabled: true,
+            acc: 0,
+            trace: trace.then(Vec::new),
         }
     }
 
-    pub fn muls(&self) -> Vec<u32> {
-        let mul_pattern = r"mul\((\d+),(\d+)\)";
-        let rgx = Regex::new(mul_pattern).expect("ERROR: Memory::muls(): incorrect regex?");
-
-        let mul_ops: Vec<(u32, u32)> = rgx
-            .captures_iter(&self.data)
-            .filter_map(|cap| Some((cap.get(1).unwrap(), cap.get(2).unwrap())))
-            .filter_map(|(op1m, op2m)| {
-                Some((
-                    op1m.as_str().parse::<u32>().unwrap_or(u32::MAX),
-                    op2m.as_str().parse::<u32>().unwrap_or(u32::MAX),
-                ))
-            })
-            .collect::<Vec<(u32, u32)>>();
-
-        mul_ops.iter().map(|(op1, op2)| *op1 * *op2).collect()
+    pub fn execute(&mut self, instruction: &Instruction) -> Result<(), String> {
+        let effect = (instruction.opcode.exec)(self, &instruction.args).map_err(|e| {
+            format!(
+                "{} at offset {}: {}",
+                instruction.text(),
+                instruction.offset,
+                e
+            )
+        })?;
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(format!(
+                "@{:<6} {:<16} {}",
+                instruction.offset,
+                instruction.text(),
+                effect
+            ));
+        }
+
+        Ok(())
     }
 
-    pub fn commands(mut self) -> Self {
-        let mul_pattern = r"(mul\((\d+),(\d+)\)|do\(\)|don\'t\(\))";
-        let rgx = Regex::new(mul_pattern).expect("ERROR: Memory::commands(): incorrect regex?");
-        let capsules: Vec<Capsule> = rgx
-            .captures_iter(&self.data)
-            .map(|c| {
-                let mut tempcap = Capsule::default();
-                if let Some(cmd) = c.get(1) {
-                    if cmd.as_str().starts_with("mul") {
-                        tempcap.cmd = "mul".to_owned();
-                    } else if cmd.as_str().starts_with("don't") {
-                        tempcap.cmd = "dont".to_owned()
-                    } else if cmd.as_str().starts_with("do") {
-                        tempcap.cmd = "do".to_owned()
-                    } else {
-                        unreachable!(
-                            "Memory::commands(): command is none of the expected options!"
-                        );
-                    }
-                }
-                tempcap.op1 = if let Some(op1) = c.get(2) {
-                    op1.as_str().to_owned()
-                } else {
-                    "".to_owned()
-                };
-                tempcap.op2 = if let Some(op2) = c.get(3) {
-                    op2.as_str().to_owned()
-                } else {
-                    "".to_owned()
-                };
-
-                tempcap
-            })
-            .collect();
-
-        self.commands = capsules.iter().map(|cp| cp.to_command()).collect();
-        self
+    fn mul(&mut self, args: &[u64]) -> Result<String, String> {
+        if !self.enabled {
+            return Ok("skipped (disabled)".to_string());
+        }
+
+        let product = args[0]
+            .checked_mul(args[1])
+            .ok_or("multiplication overflowed u64")?;
+        self.acc = self
+            .acc
+            .checked_add(product)
+            .ok_or("accumulator overflowed u64")?;
+        Ok(format!("+{} => acc {}", product, self.acc))
     }
 
-    pub fn filtered(mut self) -> Self {
-        self.filtered_muls = vec![];
-        for cmd in &self.commands {
-            match cmd {
-                CommandType::Do => self.enabled = true,
-                CommandType::Dont => self.enabled = false,
-                CommandType::Mul(op1, op2) => {
-                    if self.enabled {
-                        self.filtered_muls.push(CommandType::Mul(*op1, *op2));
-                    }
+    fn enable(&mut self, _args: &[u64]) -> Result<String, String> {
+        self.enabled = true;
+        Ok("enabled".to_string())
+    }
+
+    fn disable(&mut self, _args: &[u64]) -> Result<String, String> {
+        self.enabled = false;
+        Ok("disabled".to_string())
+    }
+}
+
+#[derive(Debug)]
+struct Memory<'a> {
+    data: &'a str,
+}
+
+impl<'a> FromInput<'a> for Memory<'a> {
+    fn from_input(input: &Input<'a>) -> Result<Self, String> {
+        Ok(Self { data: input.raw() })
+    }
+}
+
+impl Memory<'_> {
+    pub fn tokenize(&self, opcodes: &[Opcode]) -> Vec<Instruction> {
+        // Longer names first, so "don't" is not shadowed by "do".
+        let mut opcodes = opcodes.to_vec();
+        opcodes.sort_by_key(|op| std::cmp::Reverse(op.name.len()));
+
+        let mut instructions: Vec<Instruction> = vec![];
+        let mut offset = 0;
+        while offset < self.data.len() {
+            let parsed = opcodes.iter().find_map(|op| {
+                Self::parse_call(&self.data[offset..], op).map(|(args, len)| {
+                    (
+                        Instruction {
+                            opcode: *op,
+                            args,
+                            offset,
+                        },
+                        len,
+                    )
+                })
+            });
+
+            match parsed {
+                Some((instruction, len)) => {
+                    instructions.push(instruction);
+                    offset += len;
+                }
+                None => {
+                    offset += self.data[offset..].chars().next().map_or(1, char::len_utf8);
                 }
             }
         }
 
-        self
+        instructions
     }
 
-    pub fn run(&self) -> Vec<u32> {
-        self.filtered_muls
-            .iter()
-            .filter(|ct| matches!(*ct, CommandType::Mul(_, _)))
-            .filter_map(|mul| Self::run_mul(&mul))
-            .collect()
+    // Parses `name(arg,...)` at the start of `text`, returning the args and consumed length.
+    fn parse_call(text: &str, opcode: &Opcode) -> Option<(Vec<u64>, usize)> {
+        let rest = text.strip_prefix(opcode.name)?.strip_prefix('(')?;
+        let close = rest.find(')')?;
+        let inner = &rest[..close];
+        let args: Vec<u64> = if inner.is_empty() {
+            vec![]
+        } else {
+            inner
+                .split(',')
+                .map(|a| {
+                    if a.is_empty() || !a.bytes().all(|b| b.is_ascii_digit()) {
+                        return None;
+                    }
+                    a.parse::<u64>().ok()
+                })
+                .collect::<Option<_>>()?
+        };
+
+        if args.len() != opcode.arity {
+            return None;
+        }
+        Some((args, opcode.name.len() + 1 + close + 1))
     }
 
-    fn run_mul(cmd: &CommandType) -> Option<u32> {
-        match *cmd {
-            CommandType::Mul(op1, op2) => Some(op1 * op2),
-            _ => None,
+    pub fn run(&self, opcodes: &[Opcode], trace: bool) -> Result<Machine, String> {
+        let mut machine = Machine::new(trace);
+        for instruction in self.tokenize(opcodes) {
+            machine.execute(&instruction)?;
         }
+
+        Ok(machine)
     }
 }
 
-fn part1(lines: &Vec<String>) -> String {
-    Memory::new(lines)
-        .muls()
-        .into_iter()
-        .sum::<u32>()
-        .to_string()
+fn evaluate(memory: &Memory, opcodes: &[Opcode]) -> Result<u64, String> {
+    let machine = memory.run(opcodes, env::var_os("AOC_TRACE").is_some())?;
+    for line in machine.trace.iter().flatten() {
+        eprintln!("{}", line);
+    }
+
+    Ok(machine.acc)
+}
+
+fn part1(memory: &Memory) -> Result<u64, String> {
+    evaluate(memory, &[MUL])
 }
 
-fn part2(lines: &Vec<String>) -> String {
-    Memory::new(lines)
-        .commands()
-        .filtered()
-        .run()
-        .iter()
-        .map(|e| *e as u64)
-        .sum::<u64>()
-        .to_string()
+fn part2(memory: &Memory) -> Result<u64, String> {
+    evaluate(memory, &[MUL, DO, DONT])
 }
 
 fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
-
-    let sample_res = match &inputs.sample {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+    aoc::run(YEAR, DAY, part1, part2);
 }
@@ -1,22 +1,22 @@
 const YEAR: &'static str = "2024";
 const DAY: &'static str = "04";
 
-use aoc::{CharField, CharFieldDirection};
+use aoc::{CharField, CharFieldDirection, FromInput, Input};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct WordSearch {
     pub field: CharField,
     pub count: u64,
 }
-impl WordSearch {
-    pub fn new(lines: &Vec<String>) -> Self {
-        Self {
-            field: CharField::from_lines(lines)
-                .expect("ERROR: CharField::new(): couldn't create a CharField from these lines!"),
+impl FromInput<'_> for WordSearch {
+    fn from_input(input: &Input) -> Result<Self, String> {
+        Ok(Self {
+            field: CharField::from_input(input)?,
             count: 0,
-        }
+        })
     }
-
+}
+impl WordSearch {
     pub fn horizontal_forward(mut self, word: &str) -> Self {
         for row_index in 0..self.field.num_rows() {
             for col_index in 0..self.field.num_cols() - word.len() + 1 {
@@ -207,8 +207,9 @@ impl WordSearch {
     }
 }
 
-fn part1(lines: &Vec<String>) -> String {
-    WordSearch::new(lines)
+fn part1(search: &WordSearch) -> String {
+    search
+        .clone()
         .horizontal_forward("XMAS")
         .horizontal_backwards("XMAS")
         .vertical_down("XMAS")
@@ -221,28 +222,10 @@ fn part1(lines: &Vec<String>) -> String {
         .to_string()
 }
 
-fn part2(lines: &Vec<String>) -> String {
-    WordSearch::new(lines).x_mases().counted().to_string()
+fn part2(search: &WordSearch) -> String {
+    search.clone().x_mases().counted().to_string()
 }
 
 fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
-
-    let sample_res = match &inputs.sample {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+    aoc::run(YEAR, DAY, part1, part2);
 }
@@ -3,185 +3,135 @@ const DAY: &'static str = "05";
 
 use std::collections::HashMap;
 
-use aoc::Conv;
+use aoc::{Conv, FromInput, Graph, Input};
 
 #[derive(Debug)]
 struct Pages {
-    rules: HashMap<u32, Vec<u32>>,
+    // edge a -> b: page a must come before page b
+    rules: Graph<u32>,
     pages: Vec<Vec<u32>>,
-    validated_pages: Vec<Vec<u32>>,
-    invalidated_pages: Vec<Vec<u32>>,
 }
 
-impl Pages {
-    pub fn new(lines: &Vec<String>) -> Self {
-        Self {
-            rules: Self::collect_rules(lines),
-            pages: Self::collect_pages(lines),
-            validated_pages: vec![],
-            invalidated_pages: vec![],
-        }
+impl FromInput<'_> for Pages {
+    fn from_input(input: &Input) -> Result<Self, String> {
+        let (rule_lines, page_lines) = Conv::two_sections(input.lines())?;
+
+        Ok(Self {
+            rules: Self::collect_rules(rule_lines)?,
+            pages: Self::collect_pages(page_lines)?,
+        })
     }
+}
 
-    pub fn validate_pages(mut self) -> Self {
-        self.validated_pages = self
-            .pages
+impl Pages {
+    pub fn validated(&self) -> Vec<&Vec<u32>> {
+        self.pages
             .iter()
-            .filter_map(|manual| self.validate_manual(manual, &self.rules))
-            .collect();
-
-        self
+            .filter(|manual| self.is_ordered(manual))
+            .collect()
     }
 
-    pub fn invalidate_pages(mut self) -> Self {
-        self.invalidated_pages = self
-            .pages
+    pub fn reordered_invalid(&self) -> Result<Vec<Vec<u32>>, String> {
+        self.pages
             .iter()
-            .filter_map(|manual| {
-                if self.validate_manual(manual, &self.rules).is_none() {
-                    Some(manual.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        self
+            .filter(|manual| !self.is_ordered(manual))
+            .map(|manual| self.reorder_manual(manual))
+            .collect()
     }
 
-    pub fn reorder_invalid(mut self) -> Self {
-        let mut invalid_clone = self.invalidated_pages.clone();
-        for manual in &mut invalid_clone {
-            self.reorder_manual(manual);
-        }
-
-        self.invalidated_pages = invalid_clone;
-        self
+    fn after(&self, page: u32) -> impl Iterator<Item = u32> + '_ {
+        self.rules
+            .node_id(&page)
+            .into_iter()
+            .flat_map(|id| self.rules.neighbours(id))
+            .map(|(next, _)| *self.rules.node(next))
     }
 
-    pub fn middles(&self) -> Vec<u32> {
-        self.validated_pages
-            .iter()
-            .map(|manual| *manual.get((manual.len() - 1) / 2).unwrap())
-            .collect::<Vec<_>>()
-    }
+    fn is_ordered(&self, manual: &[u32]) -> bool {
+        let positions: HashMap<u32, usize> =
+            manual.iter().enumerate().map(|(i, p)| (*p, i)).collect();
 
-    pub fn invalid_middles(&self) -> Vec<u32> {
-        self.invalidated_pages
-            .iter()
-            .map(|manual| *manual.get((manual.len() - 1) / 2).unwrap())
-            .collect::<Vec<_>>()
+        manual.iter().enumerate().all(|(index, page)| {
+            self.after(*page)
+                .all(|next| positions.get(&next).is_none_or(|pos| *pos > index))
+        })
     }
 
-    fn reorder_manual(&self, manual: &mut Vec<u32>) {
-        let mut empty_vec: Vec<u32> = vec![];
-        let mut rules_clone = self.rules.clone();
-        'validate_loop: while self.validate_manual(manual, &rules_clone).is_none() {
-            for (index, page) in manual.iter().enumerate() {
-                let my_rules = rules_clone.get_mut(page).unwrap_or(&mut empty_vec);
-                let after_me = &manual[index + 1..manual.len()];
-                for (after_index, after_num) in after_me.iter().enumerate() {
-                    if my_rules.contains(after_num) {
-                        manual.swap(index, index + 1 + after_index);
-                        continue 'validate_loop;
-                    }
+    // Topological sort over the rules restricted to this manual's pages.
+    fn reorder_manual(&self, manual: &[u32]) -> Result<Vec<u32>, String> {
+        let mut restricted: Graph<u32> = Graph::new();
+        for page in manual {
+            restricted.add_node(*page);
+        }
+        for page in manual {
+            for next in self.after(*page) {
+                if let Some(to) = restricted.node_id(&next) {
+                    let from = restricted.add_node(*page);
+                    restricted.add_edge_ids(from, to, ());
                 }
             }
         }
+
+        match restricted.topo_sort() {
+            Ok(order) => Ok(order.iter().map(|id| *restricted.node(*id)).collect()),
+            Err(cyclic) => {
+                let cyclic: Vec<String> = cyclic
+                    .iter()
+                    .map(|id| restricted.node(*id).to_string())
+                    .collect();
+                Err(format!(
+                    "Pages::reorder_manual(): rules form a cycle between pages {}!",
+                    cyclic.join(", ")
+                ))
+            }
+        }
     }
 
-    fn validate_manual(
-        &self,
-        manual: &Vec<u32>,
-        rules: &HashMap<u32, Vec<u32>>,
-    ) -> Option<Vec<u32>> {
-        let empty_vec: Vec<u32> = vec![];
-        for (index, page) in manual.iter().enumerate() {
-            let my_rules = rules.get(page).unwrap_or(&empty_vec);
-            let after_me = &manual[index + 1..manual.len()];
-            for after_num in after_me {
-                if my_rules.contains(after_num) {
-                    return None;
+    fn collect_rules(lines: &[&str]) -> Result<Graph<u32>, String> {
+        let mut rules: Graph<u32> = Graph::new();
+        for line in lines {
+            match Conv::to_u32s_sep(line, "|")?.as_slice() {
+                [before, after] => {
+                    rules.add_edge(*before, *after, ());
+                }
+                _ => {
+                    return Err(format!(
+                        "Pages::collect_rules(): rule '{}' does not consist of two pages!",
+                        line
+                    ))
                 }
             }
         }
 
-        Some(manual.clone())
+        Ok(rules)
     }
 
-    fn rule_map(rule_vec: &Vec<(u32, u32)>) -> HashMap<u32, Vec<u32>> {
-        let mut rule_map: HashMap<u32, Vec<u32>> = HashMap::new();
-        rule_vec.iter().for_each(|(before, after)| {
-            let rules = rule_map.entry(*after).or_insert(vec![]);
-            rules.push(*before);
-        });
-
-        rule_map
-    }
-
-    fn collect_rules(lines: &Vec<String>) -> HashMap<u32, Vec<u32>> {
-        Self::rule_map(
-            &lines
-                .iter()
-                .filter_map(|s| {
-                    Conv::to_u32s_sep(s, "|").ok().map(|vec| {
-                        match vec.as_slice() {
-            [a, b] => (*a, *b),
-            _ => panic!("Pages::collect_rules(): the vector does NOT consist of two elements..."),
-        }
-                    })
-                })
-                .collect(),
-        )
+    fn collect_pages(lines: &[&str]) -> Result<Vec<Vec<u32>>, String> {
+        lines.iter().map(|s| Conv::to_u32s_sep(s, ",")).collect()
     }
+}
 
-    fn collect_pages(lines: &Vec<String>) -> Vec<Vec<u32>> {
-        lines
-            .iter()
-            .filter_map(|s| Conv::to_u32s_sep(s, ",").ok())
-            .collect()
-    }
+fn middle(manual: &[u32]) -> u64 {
+    manual[(manual.len() - 1) / 2] as u64
 }
 
-fn part1(lines: &Vec<String>) -> String {
-    Pages::new(lines)
-        .validate_pages()
-        .middles()
+fn part1(pages: &Pages) -> String {
+    pages
+        .validated()
         .iter()
-        .map(|m| *m as u64)
+        .map(|manual| middle(manual))
         .sum::<u64>()
         .to_string()
 }
 
-fn part2(lines: &Vec<String>) -> String {
-    Pages::new(lines)
-        .invalidate_pages()
-        .reorder_invalid()
-        .invalid_middles()
+fn part2(pages: &Pages) -> Result<u64, String> {
+    Ok(pages
+        .reordered_invalid()?
         .iter()
-        .map(|m| *m as u64)
-        .sum::<u64>()
-        .to_string()
+        .map(|manual| middle(manual))
+        .sum::<u64>())
 }
 
 fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
-
-    let sample_res = match &inputs.sample {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+    aoc::run(YEAR, DAY, part1, part2);
 }
@@ -1,47 +1,159 @@
 const YEAR: &'static str = "2024";
 const DAY: &'static str = "06";
 
-use aoc::{CharField, Conv};
+use std::{collections::HashSet, thread};
+
+use aoc::{CharField, Cycle, FromInput, Input};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Guard {
+    pos: (usize, usize),
+    dir: Direction,
+}
 
 #[derive(Debug)]
 struct AreaMap {
     matrix: CharField,
+    guard: Guard,
+}
+
+impl FromInput<'_> for AreaMap {
+    fn from_input(input: &Input) -> Result<Self, String> {
+        let matrix = CharField::from_input(input)?;
+        let guard = Self::locate_guard(&matrix)
+            .ok_or("AreaMap::from_input(): there is no guard on the map!")?;
+
+        Ok(Self { matrix, guard })
+    }
 }
+
 impl AreaMap {
-    pub fn new(lines: &Vec<String>) -> Self {
-        Self {
-            matrix: CharField::from_lines(lines).expect("AreaMap::new(): couldn't extract char map from input!"),
+    pub fn visited(&self) -> HashSet<(usize, usize)> {
+        let mut visited: HashSet<(usize, usize)> = HashSet::new();
+        let mut guard = Some(self.guard);
+        while let Some(current) = guard {
+            visited.insert(current.pos);
+            guard = self.step(current, None);
+        }
+
+        visited
+    }
+
+    // The walk is a sequence of Option<Guard> states in which leaving the map
+    // is the fixed point None, so it loops exactly when its cycle is entered
+    // with the guard still on the map.
+    pub fn loops_with(&self, obstruction: (usize, usize)) -> bool {
+        Cycle::find(Some(self.guard), |guard| {
+            guard.and_then(|g| self.step(g, Some(obstruction)))
+        })
+        .entry()
+        .is_some()
+    }
+
+    pub fn loop_obstructions(&self) -> usize {
+        let mut candidates: Vec<(usize, usize)> = self
+            .visited()
+            .into_iter()
+            .filter(|pos| *pos != self.guard.pos)
+            .collect();
+        candidates.sort();
+
+        let workers = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = candidates.len().div_ceil(workers).max(1);
+        thread::scope(|scope| {
+            let handles: Vec<_> = candidates
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || chunk.iter().filter(|pos| self.loops_with(**pos)).count())
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|h| {
+                    h.join()
+                        .expect("AreaMap::loop_obstructions(): worker panicked!")
+                })
+                .sum()
+        })
+    }
+
+    fn step(&self, guard: Guard, obstruction: Option<(usize, usize)>) -> Option<Guard> {
+        let (row, col) = guard.pos;
+        let (ahead, next_pos) = match guard.dir {
+            Direction::Up => (self.matrix.above(row, col), (row.wrapping_sub(1), col)),
+            Direction::Right => (self.matrix.right(row, col), (row, col + 1)),
+            Direction::Down => (self.matrix.below(row, col), (row + 1, col)),
+            Direction::Left => (self.matrix.left(row, col), (row, col.wrapping_sub(1))),
+        };
+
+        let ahead = ahead.ok()?;
+        if ahead == '#' || obstruction == Some(next_pos) {
+            Some(Guard {
+                pos: guard.pos,
+                dir: guard.dir.turn_right(),
+            })
+        } else {
+            Some(Guard {
+                pos: next_pos,
+                dir: guard.dir,
+            })
         }
     }
+
+    fn locate_guard(matrix: &CharField) -> Option<Guard> {
+        for row in 0..matrix.num_rows() {
+            for col in 0..matrix.num_cols() {
+                if let Some(dir) = matrix.get(row, col).ok().and_then(Direction::from_char) {
+                    return Some(Guard {
+                        pos: (row, col),
+                        dir,
+                    });
+                }
+            }
+        }
+
+        None
+    }
 }
 
-fn part1(lines: &Vec<String>) -> String {
-    dbg!(AreaMap::new(lines));
-    "NOPE1".to_owned()
+fn part1(map: &AreaMap) -> String {
+    map.visited().len().to_string()
 }
 
-fn part2(lines: &Vec<String>) -> String {
-    "NOPE2".to_owned()
+fn part2(map: &AreaMap) -> String {
+    map.loop_obstructions().to_string()
 }
 
 fn main() {
-    let inputs = aoc::Inputs::new(YEAR, DAY);
-
-    let sample_res = match &inputs.sample {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    sample_res.print("=> Sample Results:");
-
-    let input_res = match &inputs.input {
-        Some(input) => aoc::DayResults::new(part1(input), part2(input)),
-        None => aoc::DayResults::new(
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
-        ),
-    };
-    input_res.print("=> Actual Input Results:");
+    aoc::run(YEAR, DAY, part1, part2);
 }
@@ -0,0 +1,138 @@
+use std::{
+    collections::{hash_map, HashMap},
+    hash::Hash,
+    ops::{Add, Sub},
+};
+
+// Multiset of hashable items. Counts never drop to zero: an item that would
+// is removed instead, so `len` is always the number of distinct items present.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    pub fn add_n(&mut self, item: T, count: usize) {
+        if count > 0 {
+            *self.counts.entry(item).or_insert(0) += count;
+        }
+    }
+
+    // Removes up to `count` occurrences, returning how many were removed.
+    pub fn remove_n(&mut self, item: &T, count: usize) -> usize {
+        let Some(current) = self.counts.get_mut(item) else {
+            return 0;
+        };
+
+        let removed = count.min(*current);
+        *current -= removed;
+        if *current == 0 {
+            self.counts.remove(item);
+        }
+        removed
+    }
+
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.counts.contains_key(item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, T, usize> {
+        self.counts.iter()
+    }
+
+    pub fn merge(&mut self, other: Counter<T>) {
+        for (item, count) in other.counts {
+            self.add_n(item, count);
+        }
+    }
+
+    // Highest counts first; equal counts are ordered by item so the result is
+    // deterministic. `None` returns every item.
+    pub fn most_common(&self, limit: Option<usize>) -> Vec<(&T, usize)>
+    where
+        T: Ord,
+    {
+        let mut items: Vec<(&T, usize)> = self.counts.iter().map(|(i, c)| (i, *c)).collect();
+        items.sort_by(|(a, ca), (b, cb)| cb.cmp(ca).then_with(|| a.cmp(b)));
+        items.truncate(limit.unwrap_or(items.len()));
+        items
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Counter<T>;
+
+    fn add(mut self, other: Counter<T>) -> Counter<T> {
+        self.merge(other);
+        self
+    }
+}
+
+// Multiset difference: counts saturate at zero rather than going negative.
+impl<T: Eq + Hash> Sub for Counter<T> {
+    type Output = Counter<T>;
+
+    fn sub(mut self, other: Counter<T>) -> Counter<T> {
+        for (item, count) in &other.counts {
+            self.remove_n(item, *count);
+        }
+        self
+    }
+}
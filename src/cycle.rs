@@ -0,0 +1,129 @@
+use std::{collections::HashMap, hash::Hash};
+
+// The sequence initial, step(initial), step(step(initial)), ... of a
+// deterministic simulation. Over a finite state space it must eventually
+// repeat: states[start] is the first state seen twice, and from there on the
+// sequence loops every `length` steps.
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    pub start: usize,
+    pub length: usize,
+    states: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Cycle<S> {
+    // Hash-of-state detection: finds the cycle in start + length steps, at the
+    // cost of keeping every state seen along the way. Never returns if the
+    // sequence does not repeat.
+    pub fn find<F>(initial: S, mut step: F) -> Self
+    where
+        F: FnMut(&S) -> S,
+    {
+        let mut seen: HashMap<S, usize> = HashMap::new();
+        let mut states: Vec<S> = vec![];
+        let mut state = initial;
+        loop {
+            if let Some(start) = seen.get(&state) {
+                return Self {
+                    start: *start,
+                    length: states.len() - start,
+                    states,
+                };
+            }
+
+            seen.insert(state.clone(), states.len());
+            let next = step(&state);
+            states.push(state);
+            state = next;
+        }
+    }
+}
+
+impl<S> Cycle<S> {
+    // Maps step `n` onto the equivalent step before the first repeat.
+    pub fn index_at(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.index_at(n)]
+    }
+
+    // The first state of the loop.
+    pub fn entry(&self) -> &S {
+        &self.states[self.start]
+    }
+
+    // States before the loop, followed by one pass around it.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+// Runs the simulation for `n` steps, skipping ahead as soon as a state
+// repeats, so huge step counts cost no more than finding the cycle.
+pub fn state_after<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = vec![];
+    let mut state = initial;
+    for index in 0..n {
+        if let Some(start) = seen.get(&state) {
+            let cycle = Cycle {
+                start: *start,
+                length: index - start,
+                states,
+            };
+            return cycle.state_at(n).clone();
+        }
+
+        seen.insert(state.clone(), index);
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+// Brent's algorithm: returns (start, length) of the cycle holding only two
+// states at a time, for states that are large or not hashable.
+pub fn brent<S, F>(initial: S, mut step: F) -> (usize, usize)
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let (mut tortoise, mut hare) = (initial.clone(), initial);
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    (start, length)
+}
@@ -0,0 +1,142 @@
+use crate::{LinAlg, NumTheory, Rational};
+
+fn to_i128<T: TryInto<i128>>(value: T) -> Result<i128, String> {
+    value
+        .try_into()
+        .map_err(|_| "Geometry: coordinate does not fit into i128!".to_string())
+}
+
+// Parametric line `point + t * dir` in N dimensions, e.g. a hailstone's
+// position and velocity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<const N: usize> {
+    pub point: [Rational; N],
+    pub dir: [Rational; N],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Intersection<const N: usize> {
+    pub point: [Rational; N],
+    // parameter along the first line and along the second one
+    pub t: Rational,
+    pub s: Rational,
+}
+
+impl<const N: usize> Line<N> {
+    pub fn new<T: TryInto<i128>>(point: [T; N], dir: [T; N]) -> Result<Self, String> {
+        let convert = |coords: [T; N]| -> Result<[Rational; N], String> {
+            let mut result = [Rational::ZERO; N];
+            for (slot, value) in result.iter_mut().zip(coords) {
+                *slot = Rational::from(to_i128(value)?);
+            }
+            Ok(result)
+        };
+
+        Ok(Self {
+            point: convert(point)?,
+            dir: convert(dir)?,
+        })
+    }
+
+    pub fn at(&self, t: Rational) -> [Rational; N] {
+        let mut result = self.point;
+        for (coord, dir) in result.iter_mut().zip(self.dir) {
+            *coord = *coord + dir * t;
+        }
+        result
+    }
+
+    // The single point both lines pass through, or None if they are
+    // parallel, coincident or (in 3D) skew. Solves point + t*dir =
+    // other.point + s*other.dir, one equation per axis.
+    pub fn intersect(&self, other: &Line<N>) -> Option<Intersection<N>> {
+        let a: Vec<Vec<Rational>> = (0..N).map(|i| vec![self.dir[i], -other.dir[i]]).collect();
+        let b: Vec<Rational> = (0..N).map(|i| other.point[i] - self.point[i]).collect();
+        match LinAlg::solve(&a, &b).ok()?.as_slice() {
+            [t, s] => Some(Intersection {
+                point: self.at(*t),
+                t: *t,
+                s: *s,
+            }),
+            _ => None,
+        }
+    }
+}
+
+// Polygons are given as their vertices in order, either direction, with the
+// last vertex implicitly joined back to the first. Coordinates can be anything
+// that converts to i128, so CharField's (row, col) usize pairs work as is.
+pub struct Geometry;
+impl Geometry {
+    // Shoelace formula; twice the area is always an integer.
+    pub fn double_area<T>(vertices: &[(T, T)]) -> Result<i128, String>
+    where
+        T: TryInto<i128> + Copy,
+    {
+        let points = Self::points(vertices)?;
+        let overflow = || "Geometry::double_area(): i128 overflow!".to_string();
+        let mut sum: i128 = 0;
+        for (index, (x1, y1)) in points.iter().enumerate() {
+            let (x2, y2) = points[(index + 1) % points.len()];
+            let cross = x1
+                .checked_mul(y2)
+                .zip(x2.checked_mul(*y1))
+                .and_then(|(a, b)| a.checked_sub(b))
+                .ok_or_else(overflow)?;
+            sum = sum.checked_add(cross).ok_or_else(overflow)?;
+        }
+
+        Ok(sum.abs())
+    }
+
+    pub fn area<T>(vertices: &[(T, T)]) -> Result<Rational, String>
+    where
+        T: TryInto<i128> + Copy,
+    {
+        Ok(Rational::new(Self::double_area(vertices)?, 2))
+    }
+
+    // Lattice points on the polygon's edges, vertices included.
+    pub fn boundary_points<T>(vertices: &[(T, T)]) -> Result<i128, String>
+    where
+        T: TryInto<i128> + Copy,
+    {
+        let points = Self::points(vertices)?;
+        let mut count: i128 = 0;
+        for (index, (x1, y1)) in points.iter().enumerate() {
+            let (x2, y2) = points[(index + 1) % points.len()];
+            let edge = x2
+                .checked_sub(*x1)
+                .zip(y2.checked_sub(*y1))
+                .and_then(|(dx, dy)| NumTheory::checked_gcd(dx, dy));
+            count += edge.ok_or("Geometry::boundary_points(): i128 overflow!")?;
+        }
+
+        Ok(count)
+    }
+
+    // Pick's theorem: A = I + B/2 - 1, so I = (2A - B + 2) / 2.
+    pub fn interior_points<T>(vertices: &[(T, T)]) -> Result<i128, String>
+    where
+        T: TryInto<i128> + Copy,
+    {
+        Ok((Self::double_area(vertices)? - Self::boundary_points(vertices)? + 2) / 2)
+    }
+
+    fn points<T>(vertices: &[(T, T)]) -> Result<Vec<(i128, i128)>, String>
+    where
+        T: TryInto<i128> + Copy,
+    {
+        if vertices.len() < 3 {
+            return Err(format!(
+                "Geometry: a polygon needs at least 3 vertices, got {}!",
+                vertices.len()
+            ));
+        }
+
+        vertices
+            .iter()
+            .map(|(x, y)| Ok((to_i128(*x)?, to_i128(*y)?)))
+            .collect()
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Write},
+    hash::Hash,
+};
+
+pub type NodeId = usize;
+
+#[derive(Debug, Clone)]
+pub struct Graph<N, E = ()> {
+    nodes: Vec<N>,
+    index: HashMap<N, NodeId>,
+    edges: Vec<Vec<(NodeId, E)>>,
+}
+
+impl<N: Clone + Eq + Hash, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Clone + Eq + Hash, E> Graph<N, E> {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            index: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        if let Some(id) = self.index.get(&node) {
+            return *id;
+        }
+
+        let id = self.nodes.len();
+        self.index.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        id
+    }
+
+    pub fn node_id(&self, node: &N) -> Option<NodeId> {
+        self.index.get(node).copied()
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, weight: E) -> (NodeId, NodeId) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.add_edge_ids(from, to, weight);
+        (from, to)
+    }
+
+    pub fn add_edge_ids(&mut self, from: NodeId, to: NodeId, weight: E) {
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &E)> {
+        self.edges[id].iter().map(|(to, weight)| (*to, weight))
+    }
+
+    pub fn has_edge(&self, from: NodeId, to: NodeId) -> bool {
+        self.edges[from].iter().any(|(next, _)| *next == to)
+    }
+
+    pub fn in_degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.nodes.len()];
+        for (to, _) in self.edges.iter().flatten() {
+            degrees[*to] += 1;
+        }
+
+        degrees
+    }
+
+    // Kahn's algorithm; ties are broken by insertion order. On failure the
+    // error holds every node that sits on a cycle.
+    pub fn topo_sort(&self) -> Result<Vec<NodeId>, Vec<NodeId>> {
+        let mut in_degree = self.in_degrees();
+        let mut ready: VecDeque<NodeId> = (0..self.nodes.len())
+            .filter(|id| in_degree[*id] == 0)
+            .collect();
+
+        let mut ordered: Vec<NodeId> = vec![];
+        while let Some(id) = ready.pop_front() {
+            ordered.push(id);
+            for (next, _) in &self.edges[id] {
+                in_degree[*next] -= 1;
+                if in_degree[*next] == 0 {
+                    ready.push_back(*next);
+                }
+            }
+        }
+
+        if ordered.len() == self.nodes.len() {
+            Ok(ordered)
+        } else {
+            Err(self.cyclic_nodes())
+        }
+    }
+
+    // Tarjan's algorithm, iterative so deep graphs can't overflow the stack.
+    // Components come out in reverse topological order.
+    pub fn sccs(&self) -> Vec<Vec<NodeId>> {
+        let count = self.nodes.len();
+        let mut index: Vec<Option<usize>> = vec![None; count];
+        let mut low = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack: Vec<NodeId> = vec![];
+        let mut next_index = 0;
+        let mut components: Vec<Vec<NodeId>> = vec![];
+
+        for root in 0..count {
+            if index[root].is_some() {
+                continue;
+            }
+
+            let mut calls: Vec<(NodeId, usize)> = vec![(root, 0)];
+            index[root] = Some(next_index);
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(frame) = calls.last_mut() {
+                let node = frame.0;
+                if let Some((next, _)) = self.edges[node].get(frame.1) {
+                    frame.1 += 1;
+                    match index[*next] {
+                        None => {
+                            index[*next] = Some(next_index);
+                            low[*next] = next_index;
+                            next_index += 1;
+                            stack.push(*next);
+                            on_stack[*next] = true;
+                            calls.push((*next, 0));
+                        }
+                        Some(next_idx) if on_stack[*next] => {
+                            low[node] = low[node].min(next_idx);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some((parent, _)) = calls.last() {
+                    low[*parent] = low[*parent].min(low[node]);
+                }
+                if Some(low[node]) == index[node] {
+                    let mut component: Vec<NodeId> = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    pub fn cyclic_nodes(&self) -> Vec<NodeId> {
+        let mut cyclic: Vec<NodeId> = self
+            .sccs()
+            .into_iter()
+            .filter(|c| c.len() > 1 || self.has_edge(c[0], c[0]))
+            .flatten()
+            .collect();
+        cyclic.sort();
+        cyclic
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        !self.cyclic_nodes().is_empty()
+    }
+
+    pub fn reachable(&self, from: NodeId) -> HashSet<NodeId> {
+        let mut seen: HashSet<NodeId> = HashSet::from([from]);
+        let mut queue: VecDeque<NodeId> = VecDeque::from([from]);
+        while let Some(id) = queue.pop_front() {
+            for (next, _) in &self.edges[id] {
+                if seen.insert(*next) {
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        seen
+    }
+
+    pub fn to_dot_with<F, G>(&self, node_label: F, edge_label: G) -> String
+    where
+        F: Fn(&N) -> String,
+        G: Fn(&E) -> Option<String>,
+    {
+        let escape = |s: String| s.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut dot = String::from("digraph {\n");
+        for (id, node) in self.nodes.iter().enumerate() {
+            writeln!(dot, "    {} [label=\"{}\"];", id, escape(node_label(node))).unwrap();
+        }
+        for (from, edges) in self.edges.iter().enumerate() {
+            for (to, weight) in edges {
+                match edge_label(weight) {
+                    Some(label) => {
+                        writeln!(dot, "    {} -> {} [label=\"{}\"];", from, to, escape(label))
+                    }
+                    None => writeln!(dot, "    {} -> {};", from, to),
+                }
+                .unwrap();
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+impl<N: Clone + Eq + Hash + fmt::Display, E> Graph<N, E> {
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(|n| n.to_string(), |_| None)
+    }
+}
+
+impl<E> Graph<String, E> {
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        match self.index.get(name) {
+            Some(id) => *id,
+            None => self.add_node(name.to_string()),
+        }
+    }
+}
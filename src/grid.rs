@@ -0,0 +1,366 @@
+use std::{
+    collections::{hash_map, HashMap},
+    fmt,
+    ops::{Add, Sub},
+};
+
+use crate::CharField;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Point {
+    pub fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+
+    pub fn above(&self) -> Self {
+        Self::new(self.row - 1, self.col)
+    }
+
+    pub fn below(&self) -> Self {
+        Self::new(self.row + 1, self.col)
+    }
+
+    pub fn left(&self) -> Self {
+        Self::new(self.row, self.col - 1)
+    }
+
+    pub fn right(&self) -> Self {
+        Self::new(self.row, self.col + 1)
+    }
+
+    pub fn diag_ul(&self) -> Self {
+        Self::new(self.row - 1, self.col - 1)
+    }
+
+    pub fn diag_ur(&self) -> Self {
+        Self::new(self.row - 1, self.col + 1)
+    }
+
+    pub fn diag_dl(&self) -> Self {
+        Self::new(self.row + 1, self.col - 1)
+    }
+
+    pub fn diag_dr(&self) -> Self {
+        Self::new(self.row + 1, self.col + 1)
+    }
+
+    pub fn neighbours(&self) -> [Point; 4] {
+        [self.above(), self.right(), self.below(), self.left()]
+    }
+
+    pub fn all_neighbours(&self) -> [Point; 8] {
+        [
+            self.diag_ul(),
+            self.above(),
+            self.diag_ur(),
+            self.left(),
+            self.right(),
+            self.diag_dl(),
+            self.below(),
+            self.diag_dr(),
+        ]
+    }
+
+    pub fn manhattan(&self, other: &Point) -> u64 {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.row - rhs.row, self.col - rhs.col)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self::new(row as i64, col as i64)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+    bounds: Option<(Point, Point)>,
+    // rectangle the bounds never shrink below, even if it holds only defaults
+    frame: Option<(Point, Point)>,
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+            frame: None,
+        }
+    }
+
+    // Keeps `min..=max` inside the bounds from now on, so a region that is all
+    // default cells still counts towards num_rows/num_cols and rendering.
+    pub fn reserve_bounds(&mut self, min: Point, max: Point) {
+        self.frame = Some(match self.frame {
+            None => (min, max),
+            Some(frame) => Self::extend(frame, (min, max)),
+        });
+        self.bounds = Some(match self.bounds {
+            None => (min, max),
+            Some(bounds) => Self::extend(bounds, (min, max)),
+        });
+    }
+
+    pub fn default_cell(&self) -> &T {
+        &self.default
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, pos: Point) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    pub fn is_set(&self, pos: Point) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn set(&mut self, pos: Point, value: T) -> Option<T> {
+        if value == self.default {
+            return self.remove(pos);
+        }
+
+        self.bounds = Some(match self.bounds {
+            None => (pos, pos),
+            Some(bounds) => Self::extend(bounds, (pos, pos)),
+        });
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Point) -> Option<T> {
+        let removed = self.cells.remove(&pos);
+        if removed.is_some() {
+            if let Some((min, max)) = self.bounds {
+                if pos.row == min.row
+                    || pos.row == max.row
+                    || pos.col == min.col
+                    || pos.col == max.col
+                {
+                    self.recompute_bounds();
+                }
+            }
+        }
+
+        removed
+    }
+
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.row - min.row + 1) as usize)
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.col - min.col + 1) as usize)
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    pub fn above(&self, pos: Point) -> &T {
+        self.get(pos.above())
+    }
+
+    pub fn below(&self, pos: Point) -> &T {
+        self.get(pos.below())
+    }
+
+    pub fn left(&self, pos: Point) -> &T {
+        self.get(pos.left())
+    }
+
+    pub fn right(&self, pos: Point) -> &T {
+        self.get(pos.right())
+    }
+
+    pub fn diag_ul(&self, pos: Point) -> &T {
+        self.get(pos.diag_ul())
+    }
+
+    pub fn diag_ur(&self, pos: Point) -> &T {
+        self.get(pos.diag_ur())
+    }
+
+    pub fn diag_dl(&self, pos: Point) -> &T {
+        self.get(pos.diag_dl())
+    }
+
+    pub fn diag_dr(&self, pos: Point) -> &T {
+        self.get(pos.diag_dr())
+    }
+
+    pub fn neighbours(&self, pos: Point) -> impl Iterator<Item = (Point, &T)> {
+        pos.neighbours().into_iter().map(|p| (p, self.get(p)))
+    }
+
+    pub fn all_neighbours(&self, pos: Point) -> impl Iterator<Item = (Point, &T)> {
+        pos.all_neighbours().into_iter().map(|p| (p, self.get(p)))
+    }
+
+    pub fn render<F: Fn(&T) -> char>(&self, to_char: F) -> String {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+
+        (min.row..=max.row)
+            .map(|row| {
+                (min.col..=max.col)
+                    .map(|col| to_char(self.get(Point::new(row, col))))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = self.cells.keys().fold(self.frame, |acc, pos| {
+            Some(match acc {
+                None => (*pos, *pos),
+                Some(bounds) => Self::extend(bounds, (*pos, *pos)),
+            })
+        });
+    }
+
+    fn extend((min, max): (Point, Point), (low, high): (Point, Point)) -> (Point, Point) {
+        (
+            Point::new(min.row.min(low.row), min.col.min(low.col)),
+            Point::new(max.row.max(high.row), max.col.max(high.col)),
+        )
+    }
+}
+
+impl SparseGrid<char> {
+    // The field's full extent is reserved, so to_char_field round-trips it
+    // even when its border rows or columns hold only the default.
+    pub fn from_char_field(field: &CharField, default: char) -> Self {
+        let mut grid = Self::new(default);
+        if field.num_rows() > 0 && field.num_cols() > 0 {
+            grid.reserve_bounds(
+                Point::new(0, 0),
+                Point::new(field.num_rows() as i64 - 1, field.num_cols() as i64 - 1),
+            );
+        }
+        for (row, line) in field.field.iter().enumerate() {
+            for (col, ch) in line.iter().enumerate() {
+                grid.set(Point::new(row as i64, col as i64), *ch);
+            }
+        }
+
+        grid
+    }
+
+    // Returns the field along with its origin: field cell (row, col) is the
+    // grid cell at origin + (row, col).
+    pub fn to_char_field(&self) -> (CharField, Point) {
+        let field = match self.bounds {
+            None => vec![],
+            Some((min, max)) => (min.row..=max.row)
+                .map(|row| {
+                    (min.col..=max.col)
+                        .map(|col| *self.get(Point::new(row, col)))
+                        .collect()
+                })
+                .collect(),
+        };
+
+        let origin = self.bounds.map_or(Point::default(), |(min, _)| min);
+        (CharField { field }, origin)
+    }
+}
+
+impl<T: Clone + PartialEq + Into<char>> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|cell| cell.clone().into()))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct WrappingField<'a> {
+    base: &'a CharField,
+}
+
+impl<'a> WrappingField<'a> {
+    pub fn new(base: &'a CharField) -> Self {
+        Self { base }
+    }
+
+    pub fn base(&self) -> &'a CharField {
+        self.base
+    }
+
+    // None if the base field is empty, as there is nothing to wrap onto.
+    pub fn wrap(&self, pos: Point) -> Option<(usize, usize)> {
+        let (rows, cols) = self.dimensions()?;
+        Some((
+            pos.row.rem_euclid(rows) as usize,
+            pos.col.rem_euclid(cols) as usize,
+        ))
+    }
+
+    pub fn tile(&self, pos: Point) -> Option<Point> {
+        let (rows, cols) = self.dimensions()?;
+        Some(Point::new(
+            pos.row.div_euclid(rows),
+            pos.col.div_euclid(cols),
+        ))
+    }
+
+    pub fn get(&self, pos: Point) -> Result<char, &'static str> {
+        let (row, col) = self
+            .wrap(pos)
+            .ok_or("WrappingField::get(): the base field is empty")?;
+        self.base.get(row, col)
+    }
+
+    pub fn neighbours(&self, pos: Point) -> impl Iterator<Item = (Point, char)> + '_ {
+        pos.neighbours()
+            .into_iter()
+            .filter_map(|p| self.get(p).ok().map(|ch| (p, ch)))
+    }
+
+    pub fn all_neighbours(&self, pos: Point) -> impl Iterator<Item = (Point, char)> + '_ {
+        pos.all_neighbours()
+            .into_iter()
+            .filter_map(|p| self.get(p).ok().map(|ch| (p, ch)))
+    }
+
+    fn dimensions(&self) -> Option<(i64, i64)> {
+        let (rows, cols) = (self.base.num_rows(), self.base.num_cols());
+        (rows > 0 && cols > 0).then_some((rows as i64, cols as i64))
+    }
+}
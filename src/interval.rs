@@ -0,0 +1,176 @@
+use std::{iter, ops::Range};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMapping {
+    pub source: Range<i64>,
+    pub offset: i64,
+}
+
+impl RangeMapping {
+    pub fn new(source: Range<i64>, offset: i64) -> Self {
+        Self { source, offset }
+    }
+
+    pub fn apply(&self, value: i64) -> Option<i64> {
+        if self.source.contains(&value) {
+            Some(value + self.offset)
+        } else {
+            None
+        }
+    }
+}
+
+// Ranges are half-open, kept sorted and disjoint; touching ranges are merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.start.abs_diff(r.end)).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|r| r.end < range.start);
+        let hi = self.ranges.partition_point(|r| r.start <= range.end);
+        let (mut start, mut end) = (range.start, range.end);
+        if lo < hi {
+            start = start.min(self.ranges[lo].start);
+            end = end.max(self.ranges[hi - 1].end);
+        }
+
+        self.ranges.splice(lo..hi, iter::once(start..end));
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(index).is_some_and(|r| r.contains(&value))
+    }
+
+    pub fn contains_range(&self, range: &Range<i64>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+
+        let index = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(index)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = self.clone();
+        for range in &other.ranges {
+            result.insert(range.clone());
+        }
+
+        result
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = IntervalSet::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                result.ranges.push(start..end);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        result
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = IntervalSet::new();
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                if other.ranges[k].start > start {
+                    result.ranges.push(start..other.ranges[k].start);
+                }
+                start = start.max(other.ranges[k].end);
+                k += 1;
+            }
+
+            if start < range.end {
+                result.ranges.push(start..range.end);
+            }
+        }
+
+        result
+    }
+
+    // Mappings are tried in order; values no mapping covers pass through unchanged.
+    pub fn map_through(&self, mappings: &[RangeMapping]) -> IntervalSet {
+        let mut result = IntervalSet::new();
+        let mut pending = self.clone();
+
+        for mapping in mappings {
+            let source = IntervalSet::from(mapping.source.clone());
+            for hit in pending.intersection(&source).ranges {
+                result.insert(hit.start + mapping.offset..hit.end + mapping.offset);
+            }
+            pending = pending.difference(&source);
+        }
+
+        result.union(&pending)
+    }
+}
+
+impl From<Range<i64>> for IntervalSet {
+    fn from(range: Range<i64>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl FromIterator<Range<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+
+        set
+    }
+}
@@ -1,4 +1,35 @@
-use std::{env, fs, iter::Rev, ops::RangeInclusive};
+use std::{
+    env, fmt, fs,
+    iter::Rev,
+    ops::{Range, RangeInclusive},
+    str::FromStr,
+};
+
+mod counter;
+mod cycle;
+mod geometry;
+mod graph;
+mod grid;
+mod interval;
+mod linalg;
+mod memo;
+mod num_theory;
+mod parse;
+mod rational;
+mod runner;
+
+pub use counter::Counter;
+pub use cycle::{brent, state_after, Cycle};
+pub use geometry::{Geometry, Intersection, Line};
+pub use graph::{Graph, NodeId};
+pub use grid::{Point, SparseGrid, WrappingField};
+pub use interval::{IntervalSet, RangeMapping};
+pub use linalg::LinAlg;
+pub use memo::{Memo, MemoStats};
+pub use num_theory::NumTheory;
+pub use parse::Pattern;
+pub use rational::Rational;
+pub use runner::{run, Answer, FromInput, Lines, Matrix};
 
 #[derive(Debug)]
 pub enum CharFieldDirection {
@@ -12,65 +43,264 @@ pub enum CharFieldDirection {
     DiagDownLeft,
 }
 
-fn get_file_lines(filename: &str) -> Option<Vec<String>> {
-    match fs::read_to_string(format!(
+fn get_file_contents(filename: &str) -> Option<String> {
+    fs::read_to_string(format!(
         "{}/inputs/{}",
         env::current_dir().unwrap().display(),
         filename
-    )) {
-        Ok(s) => Some(s.lines().map(String::from).collect()),
-        Err(_) => None,
-    }
+    ))
+    .ok()
 }
 
 pub struct Inputs {
-    pub sample: Option<Vec<String>>,
-    pub input: Option<Vec<String>>,
+    pub sample: Option<String>,
+    pub input: Option<String>,
 }
 
 impl Inputs {
     pub fn new(year: &str, day: &str) -> Self {
         let sample_filename = format!("{}-day-{}-sample.txt", year, day);
         let input_filename = format!("{}-day-{}-input.txt", year, day);
-        let sample = get_file_lines(&sample_filename);
-        let input = get_file_lines(&input_filename);
+        let sample = get_file_contents(&sample_filename);
+        let input = get_file_contents(&input_filename);
 
         Self { sample, input }
     }
+
+    pub fn sample(&self) -> Option<Input<'_>> {
+        self.sample.as_deref().map(Input::new)
+    }
+
+    pub fn input(&self) -> Option<Input<'_>> {
+        self.input.as_deref().map(Input::new)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Input<'a> {
+    raw: &'a str,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(raw: &'a str) -> Self {
+        Self {
+            raw,
+            lines: raw.lines().collect(),
+        }
+    }
+
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
+    pub fn lines(&self) -> &[&'a str] {
+        &self.lines
+    }
+
+    // Sub-input made of lines[range], or None if the range is out of bounds.
+    pub fn section(&self, lines: Range<usize>) -> Option<Input<'a>> {
+        let selected = self.lines.get(lines)?;
+        let raw = match (selected.first(), selected.last()) {
+            (Some(first), Some(last)) => {
+                &self.raw[self.offset(first)..self.offset(last) + last.len()]
+            }
+            _ => "",
+        };
+
+        Some(Input {
+            raw,
+            lines: selected.to_vec(),
+        })
+    }
+
+    // Runs of lines separated by blank ones, like Conv::sections.
+    pub fn sections(&self) -> Vec<Input<'a>> {
+        let mut ranges: Vec<Range<usize>> = vec![];
+        let mut start = 0;
+        for (index, line) in self.lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                if index > start {
+                    ranges.push(start..index);
+                }
+                start = index + 1;
+            }
+        }
+        if self.lines.len() > start {
+            ranges.push(start..self.lines.len());
+        }
+
+        ranges
+            .into_iter()
+            .filter_map(|range| self.section(range))
+            .collect()
+    }
+
+    // Only valid for slices of `self.lines`, which all point into `self.raw`.
+    fn offset(&self, line: &str) -> usize {
+        line.as_ptr() as usize - self.raw.as_ptr() as usize
+    }
 }
 
 pub struct Conv;
 impl Conv {
     pub fn to_matrix(lines: &Vec<String>) -> Vec<Vec<u32>> {
-        lines
-            .iter()
-            .map(|s| {
-                s.split_whitespace()
-                    .map(|sn| {
-                        sn.parse::<u32>()
-                            .expect("Conv::to_matrix(): an element is not a number!")
-                    })
-                    .collect()
-            })
-            .collect()
+        Self::numbers_matrix(lines).expect("Conv::to_matrix(): an element is not a number!")
     }
 
     pub fn to_u32s_sep(line: &str, sep: &str) -> Result<Vec<u32>, String> {
+        Self::numbers_sep(line, sep)
+    }
+
+    pub fn numbers_sep<T>(line: &str, sep: &str) -> Result<Vec<T>, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
         line.split(sep)
-            .map(|u| {
-                u.parse::<u32>().map_err(|e| {
-                    format!(
-                        "Conv::to_u32s_sep: couldn't convert {} to u32! Error: {}",
-                        u, e
-                    )
-                })
+            .map(|token| Self::number(line, token.trim()))
+            .collect()
+    }
+
+    pub fn numbers_ws<T>(line: &str) -> Result<Vec<T>, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        line.split_whitespace()
+            .map(|token| Self::number(line, token))
+            .collect()
+    }
+
+    pub fn numbers_all<T>(line: &str) -> Result<Vec<T>, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let bytes = line.as_bytes();
+        let mut numbers: Vec<T> = vec![];
+        let mut index = 0;
+        while index < bytes.len() {
+            let negative = bytes[index] == b'-'
+                && bytes.get(index + 1).is_some_and(|b| b.is_ascii_digit())
+                && (index == 0 || !bytes[index - 1].is_ascii_digit());
+            if !negative && !bytes[index].is_ascii_digit() {
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+            index += 1;
+            while index < bytes.len() && bytes[index].is_ascii_digit() {
+                index += 1;
+            }
+            numbers.push(Self::number(line, &line[start..index])?);
+        }
+
+        Ok(numbers)
+    }
+
+    pub fn numbers_matrix<T, S>(lines: &[S]) -> Result<Vec<Vec<T>>, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+        S: AsRef<str>,
+    {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                Self::numbers_ws(line.as_ref()).map_err(|e| format!("line {}, {}", index + 1, e))
             })
             .collect()
     }
 
-    pub fn to_string(lines: &Vec<String>) -> String {
+    fn number<T>(line: &str, token: &str) -> Result<T, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        token.parse::<T>().map_err(|e| {
+            let column = token.as_ptr() as usize - line.as_ptr() as usize + 1;
+            format!(
+                "column {}: couldn't convert '{}' to {}! Error: {}",
+                column,
+                token,
+                std::any::type_name::<T>(),
+                e
+            )
+        })
+    }
+
+    // Transposes whitespace-separated rows into columns; every row must have
+    // the same number of values as the first one.
+    pub fn columns<T, S>(lines: &[S]) -> Result<Vec<Vec<T>>, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+        S: AsRef<str>,
+    {
+        let rows: Vec<Vec<T>> = Self::numbers_matrix(lines)?;
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut columns: Vec<Vec<T>> = (0..width).map(|_| vec![]).collect();
+        for (index, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "line {}: expected {} columns, found {}!",
+                    index + 1,
+                    width,
+                    row.len()
+                ));
+            }
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+
+        Ok(columns)
+    }
+
+    pub fn paired_columns<T, S>(lines: &[S]) -> Result<(Vec<T>, Vec<T>), String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+        S: AsRef<str>,
+    {
+        let mut columns = Self::columns(lines)?;
+        match columns.len() {
+            0 => Ok((vec![], vec![])),
+            2 => {
+                let right = columns.pop().unwrap();
+                let left = columns.pop().unwrap();
+                Ok((left, right))
+            }
+            other => Err(format!(
+                "Conv::paired_columns: expected 2 columns, found {}!",
+                other
+            )),
+        }
+    }
+
+    pub fn to_string(lines: &[String]) -> String {
         lines.join("\n")
     }
+
+    pub fn sections<S: AsRef<str>>(lines: &[S]) -> Vec<&[S]> {
+        lines
+            .split(|l| l.as_ref().trim().is_empty())
+            .filter(|section| !section.is_empty())
+            .collect()
+    }
+
+    pub fn two_sections<S: AsRef<str>>(lines: &[S]) -> Result<(&[S], &[S]), String> {
+        match Self::sections(lines).as_slice() {
+            [first, second] => Ok((first, second)),
+            other => Err(format!(
+                "Conv::two_sections: expected 2 blank-line-separated sections, found {}!",
+                other.len()
+            )),
+        }
+    }
 }
 
 pub struct DayResults {
@@ -94,22 +324,26 @@ impl DayResults {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CharField {
     field: Vec<Vec<char>>,
     // capacity: (usize, usize),
 }
 
 impl CharField {
-    pub fn from_lines(lines: &Vec<String>) -> Option<Self> {
-        let ex_len = lines.get(0).unwrap().len();
-        if !lines.iter().map(|l| l.len()).all(|ln| ln == ex_len) {
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Option<Self> {
+        let ex_len = lines.first()?.as_ref().len();
+        if !lines
+            .iter()
+            .map(|l| l.as_ref().len())
+            .all(|ln| ln == ex_len)
+        {
             return None;
         }
 
         let mut new_field: Vec<Vec<char>> = vec![];
         for line in lines {
-            new_field.push(line.chars().collect());
+            new_field.push(line.as_ref().chars().collect());
         }
 
         Some(Self {
@@ -214,6 +448,44 @@ impl CharField {
         Ok(self.get(row + 1, col + 1).unwrap())
     }
 
+    // In-bounds cells above, right, below and left of (row, col), in the
+    // same order as Point::neighbours.
+    pub fn neighbours(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(row, col, &[(-1, 0), (0, 1), (1, 0), (0, -1)])
+    }
+
+    // In-bounds cells among the 8 surrounding (row, col), diagonals included.
+    pub fn all_neighbours(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(
+            row,
+            col,
+            &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        )
+    }
+
+    fn offsets(
+        &self,
+        row: usize,
+        col: usize,
+        deltas: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (num_rows, num_cols) = (self.num_rows(), self.num_cols());
+        deltas.iter().filter_map(move |(dr, dc)| {
+            let r = row.checked_add_signed(*dr)?;
+            let c = col.checked_add_signed(*dc)?;
+            (r < num_rows && c < num_cols).then_some((r, c))
+        })
+    }
+
     pub fn find_word(
         &self,
         starting_pos: (usize, usize),
@@ -348,7 +620,22 @@ impl CharField {
         }
     }
 
+    pub fn wrapping(&self) -> WrappingField<'_> {
+        WrappingField::new(self)
+    }
+
     pub fn rev(upper: usize, lower: usize) -> Rev<RangeInclusive<usize>> {
         (lower..=upper).rev()
     }
 }
+
+impl fmt::Display for CharField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .field
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
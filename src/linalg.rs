@@ -0,0 +1,72 @@
+use crate::Rational;
+
+pub struct LinAlg;
+impl LinAlg {
+    // Gauss-Jordan elimination in place; returns the pivot column of each
+    // non-zero row, so the rank is the length of the result.
+    pub fn rref(matrix: &mut [Vec<Rational>]) -> Vec<usize> {
+        let cols = matrix.first().map_or(0, |row| row.len());
+        let mut pivots: Vec<usize> = vec![];
+        for col in 0..cols {
+            let row = pivots.len();
+            let Some(found) = (row..matrix.len()).find(|r| !matrix[*r][col].is_zero()) else {
+                continue;
+            };
+
+            matrix.swap(row, found);
+            let pivot = matrix[row][col];
+            for value in matrix[row].iter_mut() {
+                *value = *value / pivot;
+            }
+            let pivot_row = matrix[row].clone();
+            for (index, other) in matrix.iter_mut().enumerate() {
+                let factor = other[col];
+                if index == row || factor.is_zero() {
+                    continue;
+                }
+                for (value, pivot_value) in other.iter_mut().zip(&pivot_row).skip(col) {
+                    *value = *value - factor * *pivot_value;
+                }
+            }
+            pivots.push(col);
+        }
+
+        pivots
+    }
+
+    // Solves A x = b exactly. Over-determined systems are fine as long as
+    // they are consistent; there must be exactly one solution.
+    pub fn solve(a: &[Vec<Rational>], b: &[Rational]) -> Result<Vec<Rational>, String> {
+        let cols = a.first().map_or(0, |row| row.len());
+        if a.len() != b.len() {
+            return Err(format!(
+                "LinAlg::solve(): {} equations but {} right-hand sides!",
+                a.len(),
+                b.len()
+            ));
+        }
+        if let Some(index) = a.iter().position(|row| row.len() != cols) {
+            return Err(format!(
+                "LinAlg::solve(): row {} has {} coefficients, expected {}!",
+                index + 1,
+                a[index].len(),
+                cols
+            ));
+        }
+
+        let mut augmented: Vec<Vec<Rational>> = a
+            .iter()
+            .zip(b)
+            .map(|(row, rhs)| row.iter().copied().chain([*rhs]).collect())
+            .collect();
+        let pivots = Self::rref(&mut augmented);
+        if pivots.last() == Some(&cols) {
+            return Err("LinAlg::solve(): the system is inconsistent!".to_string());
+        }
+        if pivots.len() < cols {
+            return Err("LinAlg::solve(): the system has infinitely many solutions!".to_string());
+        }
+
+        Ok(augmented.iter().take(cols).map(|row| row[cols]).collect())
+    }
+}
@@ -0,0 +1,100 @@
+use std::{collections::HashMap, fmt, hash::Hash, rc::Rc};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            calls => self.hits as f64 / calls as f64,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+type Recursive<'a, A, R> = Rc<dyn Fn(&mut Memo<'a, A, R>, A) -> R + 'a>;
+
+// Memoized recursive function. The wrapped closure receives the memo itself
+// and recurses through `Memo::get`, so every call, nested ones included, goes
+// through the cache. Several arguments are passed as a tuple.
+pub struct Memo<'a, A, R> {
+    func: Recursive<'a, A, R>,
+    cache: HashMap<A, R>,
+    stats: Option<MemoStats>,
+}
+
+impl<'a, A: Clone + Eq + Hash, R: Clone> Memo<'a, A, R> {
+    pub fn new<F>(func: F) -> Self
+    where
+        F: Fn(&mut Memo<'a, A, R>, A) -> R + 'a,
+    {
+        Self {
+            func: Rc::new(func),
+            cache: HashMap::new(),
+            stats: None,
+        }
+    }
+
+    pub fn with_stats(mut self) -> Self {
+        self.stats = Some(MemoStats::default());
+        self
+    }
+
+    pub fn get(&mut self, args: A) -> R {
+        if let Some(result) = self.cache.get(&args) {
+            if let Some(stats) = self.stats.as_mut() {
+                stats.hits += 1;
+            }
+            return result.clone();
+        }
+
+        if let Some(stats) = self.stats.as_mut() {
+            stats.misses += 1;
+        }
+        let func = Rc::clone(&self.func);
+        let result = func(self, args.clone());
+        self.cache.insert(args, result.clone());
+        result
+    }
+
+    pub fn stats(&self) -> Option<MemoStats> {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    // Drops cached results but keeps the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<A: fmt::Debug, R: fmt::Debug> fmt::Debug for Memo<'_, A, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Memo")
+            .field("cache", &self.cache)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,171 @@
+// The i64 helpers compute through i128, so intermediate products can't
+// overflow; they panic only if the final result doesn't fit back into an i64.
+// The checked_* variants work on i128 throughout and report overflow instead.
+pub struct NumTheory;
+impl NumTheory {
+    pub fn gcd(a: i64, b: i64) -> i64 {
+        Self::narrow(Self::checked_gcd(a as i128, b as i128), "gcd")
+    }
+
+    pub fn lcm(a: i64, b: i64) -> i64 {
+        Self::narrow(Self::checked_lcm(a as i128, b as i128), "lcm")
+    }
+
+    pub fn gcd_all<I: IntoIterator<Item = i64>>(values: I) -> i64 {
+        values.into_iter().fold(0, Self::gcd)
+    }
+
+    pub fn lcm_all<I: IntoIterator<Item = i64>>(values: I) -> i64 {
+        let values = values.into_iter().map(|v| v as i128);
+        Self::narrow(Self::checked_lcm_all(values), "lcm_all")
+    }
+
+    // Returns (g, x, y) with a*x + b*y = g = gcd(a, b).
+    pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+        let (g, x, y) = Self::extended_gcd_i128(a as i128, b as i128);
+        (
+            Self::narrow(Some(g), "extended_gcd"),
+            Self::narrow(Some(x), "extended_gcd"),
+            Self::narrow(Some(y), "extended_gcd"),
+        )
+    }
+
+    pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+        Self::checked_mod_inverse(a as i128, modulus as i128).map(|inv| inv as i64)
+    }
+
+    pub fn mod_pow(base: i64, exp: u64, modulus: i64) -> i64 {
+        Self::narrow(
+            Self::checked_mod_pow(base as i128, exp as u128, modulus as i128),
+            "mod_pow",
+        )
+    }
+
+    // Solves x = r (mod m) for every (r, m) pair. The moduli need not be
+    // coprime; returns (x, lcm of the moduli) with 0 <= x < lcm.
+    pub fn crt(congruences: &[(i64, i64)]) -> Result<(i64, i64), String> {
+        let wide: Vec<(i128, i128)> = congruences
+            .iter()
+            .map(|(r, m)| (*r as i128, *m as i128))
+            .collect();
+        let (x, modulus) = Self::checked_crt(&wide)?;
+        match (i64::try_from(x), i64::try_from(modulus)) {
+            (Ok(x), Ok(modulus)) => Ok((x, modulus)),
+            _ => Err(format!(
+                "NumTheory::crt(): solution modulo {} overflows i64!",
+                modulus
+            )),
+        }
+    }
+
+    pub fn checked_gcd(a: i128, b: i128) -> Option<i128> {
+        let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+
+        i128::try_from(a).ok()
+    }
+
+    pub fn checked_lcm(a: i128, b: i128) -> Option<i128> {
+        if a == 0 || b == 0 {
+            return Some(0);
+        }
+
+        (a / Self::checked_gcd(a, b)?).checked_mul(b)?.checked_abs()
+    }
+
+    pub fn checked_lcm_all<I: IntoIterator<Item = i128>>(values: I) -> Option<i128> {
+        values.into_iter().try_fold(1, Self::checked_lcm)
+    }
+
+    pub fn checked_mod_mul(a: i128, b: i128, modulus: i128) -> Option<i128> {
+        a.checked_mul(b)?.checked_rem_euclid(modulus)
+    }
+
+    pub fn checked_mod_pow(base: i128, mut exp: u128, modulus: i128) -> Option<i128> {
+        let mut base = base.checked_rem_euclid(modulus)?;
+        let mut result = 1i128.checked_rem_euclid(modulus)?;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = Self::checked_mod_mul(result, base, modulus)?;
+            }
+            base = Self::checked_mod_mul(base, base, modulus)?;
+            exp >>= 1;
+        }
+
+        Some(result)
+    }
+
+    pub fn checked_mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+        if modulus <= 0 {
+            return None;
+        }
+
+        match Self::extended_gcd_i128(a.rem_euclid(modulus), modulus) {
+            (1, x, _) => Some(x.rem_euclid(modulus)),
+            _ => None,
+        }
+    }
+
+    pub fn checked_crt(congruences: &[(i128, i128)]) -> Result<(i128, i128), String> {
+        let overflow = || "NumTheory::checked_crt(): i128 overflow!".to_string();
+
+        let mut solution: (i128, i128) = (0, 1);
+        for (residue, modulus) in congruences {
+            if *modulus <= 0 {
+                return Err(format!(
+                    "NumTheory::checked_crt(): modulus {} is not positive!",
+                    modulus
+                ));
+            }
+
+            let (x, m) = solution;
+            let residue = residue.rem_euclid(*modulus);
+            let (g, p, _) = Self::extended_gcd_i128(m, *modulus);
+            let diff = residue - x;
+            if diff % g != 0 {
+                return Err(format!(
+                    "NumTheory::checked_crt(): x = {} (mod {}) contradicts x = {} (mod {})!",
+                    residue, modulus, x, m
+                ));
+            }
+
+            // x + m * t = residue (mod modulus) => t = diff/g * p (mod modulus/g)
+            let step = modulus / g;
+            let t = Self::checked_mod_mul(diff / g, p, step).ok_or_else(overflow)?;
+            let lcm = (m / g).checked_mul(*modulus).ok_or_else(overflow)?;
+            let next = m
+                .checked_mul(t)
+                .and_then(|mt| x.checked_add(mt))
+                .ok_or_else(overflow)?;
+            solution = (next.rem_euclid(lcm), lcm);
+        }
+
+        Ok(solution)
+    }
+
+    fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+        let (mut old_r, mut r) = (a, b);
+        let (mut old_s, mut s) = (1, 0);
+        let (mut old_t, mut t) = (0, 1);
+        while r != 0 {
+            let quotient = old_r / r;
+            (old_r, r) = (r, old_r - quotient * r);
+            (old_s, s) = (s, old_s - quotient * s);
+            (old_t, t) = (t, old_t - quotient * t);
+        }
+
+        if old_r < 0 {
+            (-old_r, -old_s, -old_t)
+        } else {
+            (old_r, old_s, old_t)
+        }
+    }
+
+    fn narrow(value: Option<i128>, name: &str) -> i64 {
+        value
+            .and_then(|v| i64::try_from(v).ok())
+            .unwrap_or_else(|| panic!("NumTheory::{}(): result overflows i64!", name))
+    }
+}
@@ -0,0 +1,111 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    format: String,
+    literals: Vec<String>,
+}
+
+impl Pattern {
+    pub fn new(format: &str) -> Self {
+        let literals: Vec<String> = format.split("{}").map(String::from).collect();
+        // only the literals between two fields must be non-empty
+        let inner = literals.len().saturating_sub(2);
+        if literals.iter().skip(1).take(inner).any(|l| l.is_empty()) {
+            panic!(
+                "Pattern::new(): fields in '{}' must be separated by literals!",
+                format
+            );
+        }
+
+        Self {
+            format: format.to_string(),
+            literals,
+        }
+    }
+
+    pub fn num_fields(&self) -> usize {
+        self.literals.len() - 1
+    }
+
+    pub fn fields<'a>(&self, line: &'a str) -> Result<Vec<&'a str>, String> {
+        let mut rest = line
+            .strip_prefix(self.literals[0].as_str())
+            .ok_or_else(|| {
+                format!(
+                    "Pattern '{}': '{}' does not start with '{}'!",
+                    self.format, line, self.literals[0]
+                )
+            })?;
+
+        let mut fields: Vec<&'a str> = vec![];
+        for (index, literal) in self.literals.iter().enumerate().skip(1) {
+            if literal.is_empty() {
+                fields.push(rest.trim());
+                rest = "";
+                continue;
+            }
+
+            let end = rest.find(literal.as_str()).ok_or_else(|| {
+                format!(
+                    "Pattern '{}': expected '{}' after field {} in '{}'!",
+                    self.format, literal, index, line
+                )
+            })?;
+            fields.push(rest[..end].trim());
+            rest = &rest[end + literal.len()..];
+        }
+
+        if !rest.trim().is_empty() {
+            return Err(format!(
+                "Pattern '{}': unexpected trailing '{}' in '{}'!",
+                self.format, rest, line
+            ));
+        }
+
+        Ok(fields)
+    }
+
+    pub fn convert<T>(&self, index: usize, raw: Option<&str>) -> Result<T, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let raw = raw.ok_or_else(|| {
+            format!(
+                "Pattern '{}': has {} fields, but field {} was requested!",
+                self.format,
+                self.num_fields(),
+                index + 1
+            )
+        })?;
+
+        raw.parse::<T>().map_err(|e| {
+            format!(
+                "Pattern '{}': couldn't convert field {} ('{}') to {}! Error: {}",
+                self.format,
+                index + 1,
+                raw,
+                std::any::type_name::<T>(),
+                e
+            )
+        })
+    }
+}
+
+#[macro_export]
+macro_rules! parse {
+    ($format:literal, $line:expr => $($ty:ty),+ $(,)?) => {{
+        static PATTERN: ::std::sync::OnceLock<$crate::Pattern> = ::std::sync::OnceLock::new();
+        let pattern = PATTERN.get_or_init(|| $crate::Pattern::new($format));
+        pattern.fields($line).and_then(|fields| {
+            let mut fields = fields.into_iter().enumerate();
+            Ok(($({
+                let (index, raw) = fields
+                    .next()
+                    .map_or((pattern.num_fields(), None), |(i, f)| (i, Some(f)));
+                pattern.convert::<$ty>(index, raw)?
+            },)+))
+        })
+    }};
+}
@@ -0,0 +1,230 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::NumTheory;
+
+// Exact fraction, always stored in lowest terms with a positive denominator.
+// Arithmetic panics on i128 overflow in every build profile rather than
+// silently wrapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    pub fn new(numer: i128, denom: i128) -> Self {
+        Self::checked_new(numer, denom).expect("Rational::new(): zero denominator or overflow!")
+    }
+
+    pub fn checked_new(numer: i128, denom: i128) -> Option<Self> {
+        if denom == 0 {
+            return None;
+        }
+
+        let gcd = NumTheory::checked_gcd(numer, denom)?;
+        let (numer, denom) = (numer / gcd, denom / gcd);
+        if denom < 0 {
+            Some(Self {
+                numer: numer.checked_neg()?,
+                denom: denom.checked_neg()?,
+            })
+        } else {
+            Some(Self { numer, denom })
+        }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.numer)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numer: self.numer.abs(),
+            denom: self.denom,
+        }
+    }
+
+    pub fn recip(&self) -> Option<Self> {
+        Self::checked_new(self.denom, self.numer)
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let gcd = NumTheory::checked_gcd(self.denom, other.denom)?;
+        let (left, right) = (self.denom / gcd, other.denom / gcd);
+        let numer = self
+            .numer
+            .checked_mul(right)?
+            .checked_add(other.numer.checked_mul(left)?)?;
+        Self::checked_new(numer, self.denom.checked_mul(right)?)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    // Cross-reduces first so the products stay as small as possible.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let first = NumTheory::checked_gcd(self.numer, other.denom)?;
+        let second = NumTheory::checked_gcd(other.numer, self.denom)?;
+        Self::checked_new(
+            (self.numer / first).checked_mul(other.numer / second)?,
+            (self.denom / second).checked_mul(other.denom / first)?,
+        )
+    }
+
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        self.checked_mul(&other.recip()?)
+    }
+
+    // Compares a/b with c/d (b, d > 0) one continued fraction term at a time;
+    // every step only divides, so nothing can overflow.
+    fn cmp_expansions((mut a, mut b): (i128, i128), (mut c, mut d): (i128, i128)) -> Ordering {
+        loop {
+            let (left, right) = (a.div_euclid(b), c.div_euclid(d));
+            if left != right {
+                return left.cmp(&right);
+            }
+
+            let (left_rem, right_rem) = (a.rem_euclid(b), c.rem_euclid(d));
+            match (left_rem == 0, right_rem == 0) {
+                (true, true) => return Ordering::Equal,
+                (true, false) => return Ordering::Less,
+                (false, true) => return Ordering::Greater,
+                (false, false) => {}
+            }
+
+            // left_rem/b vs right_rem/d orders the same as d/right_rem vs b/left_rem
+            (a, b, c, d) = (d, right_rem, b, left_rem);
+        }
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            numer: self.numer.checked_neg()?,
+            denom: self.denom,
+        })
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self {
+            numer: value,
+            denom: 1,
+        }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self::from(value as i128)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        self.checked_add(&other)
+            .expect("Rational::add(): i128 overflow!")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self.checked_sub(&other)
+            .expect("Rational::sub(): i128 overflow!")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        self.checked_mul(&other)
+            .expect("Rational::mul(): i128 overflow!")
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        self.checked_div(&other)
+            .expect("Rational::div(): division by zero or i128 overflow!")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        self.checked_neg().expect("Rational::neg(): i128 overflow!")
+    }
+}
+
+// Never panics: cross-multiplies when the products fit into i128 and falls
+// back to comparing continued fraction expansions when they don't.
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let by_sign = self.numer.signum().cmp(&other.numer.signum());
+        if by_sign != Ordering::Equal {
+            return by_sign;
+        }
+
+        match (
+            self.numer.checked_mul(other.denom),
+            other.numer.checked_mul(self.denom),
+        ) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            _ => Self::cmp_expansions((self.numer, self.denom), (other.numer, other.denom)),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::{fmt, ops::Deref, str::FromStr};
+
+use crate::{CharField, Conv, DayResults, Input, Inputs};
+
+// Parsed types may borrow from the input text for its lifetime 'a.
+pub trait FromInput<'a>: Sized {
+    fn from_input(input: &Input<'a>) -> Result<Self, String>;
+}
+
+impl<T> FromInput<'_> for Vec<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn from_input(input: &Input) -> Result<Self, String> {
+        input
+            .lines()
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                line.parse::<T>()
+                    .map_err(|e| format!("line {}: {}", index + 1, e))
+            })
+            .collect()
+    }
+}
+
+impl FromInput<'_> for CharField {
+    fn from_input(input: &Input) -> Result<Self, String> {
+        CharField::from_lines(input.lines())
+            .ok_or_else(|| "CharField::from_input(): lines are not of equal length!".to_string())
+    }
+}
+
+impl<'a, A: FromInput<'a>, B: FromInput<'a>> FromInput<'a> for (A, B) {
+    fn from_input(input: &Input<'a>) -> Result<Self, String> {
+        match input.sections().as_slice() {
+            [first, second] => Ok((A::from_input(first)?, B::from_input(second)?)),
+            other => Err(format!(
+                "(A, B)::from_input(): expected 2 blank-line-separated sections, found {}!",
+                other.len()
+            )),
+        }
+    }
+}
+
+// The input's lines, borrowed rather than copied into Strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lines<'a>(pub Vec<&'a str>);
+
+impl<'a> Deref for Lines<'a> {
+    type Target = Vec<&'a str>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> FromInput<'a> for Lines<'a> {
+    fn from_input(input: &Input<'a>) -> Result<Self, String> {
+        Ok(Lines(input.lines().to_vec()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T>(pub Vec<Vec<T>>);
+
+impl<T> Deref for Matrix<T> {
+    type Target = Vec<Vec<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> FromInput<'_> for Matrix<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn from_input(input: &Input) -> Result<Self, String> {
+        Conv::numbers_matrix(input.lines()).map(Matrix)
+    }
+}
+
+pub trait Answer {
+    fn into_answer(self) -> Result<String, String>;
+}
+
+impl Answer for String {
+    fn into_answer(self) -> Result<String, String> {
+        Ok(self)
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, String> {
+        self.map(|a| a.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+fn solve<'a, I, A, B>(
+    input: &Input<'a>,
+    part1: &impl Fn(&I) -> A,
+    part2: &impl Fn(&I) -> B,
+) -> DayResults
+where
+    I: FromInput<'a>,
+    A: Answer,
+    B: Answer,
+{
+    let parsed = match I::from_input(input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let message = format!("<PARSE ERROR: {}>", e);
+            return DayResults::new(message.clone(), message);
+        }
+    };
+
+    let answer =
+        |result: Result<String, String>| result.unwrap_or_else(|e| format!("<SOLVE ERROR: {}>", e));
+    DayResults::new(
+        answer(part1(&parsed).into_answer()),
+        answer(part2(&parsed).into_answer()),
+    )
+}
+
+pub fn run<I, A, B>(year: &str, day: &str, part1: impl Fn(&I) -> A, part2: impl Fn(&I) -> B)
+where
+    I: FromInput<'static>,
+    A: Answer,
+    B: Answer,
+{
+    // A day's binary runs exactly once, so its inputs can live for the rest
+    // of the process and parsed types are free to borrow from them.
+    let inputs: &'static Inputs = Box::leak(Box::new(Inputs::new(year, day)));
+
+    let sample_res = match inputs.sample() {
+        Some(input) => solve(&input, &part1, &part2),
+        None => DayResults::new(
+            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
+            "<SAMPLE INPUT UNAVAILABLE>".to_string(),
+        ),
+    };
+    sample_res.print("=> Sample Results:");
+
+    let input_res = match inputs.input() {
+        Some(input) => solve(&input, &part1, &part2),
+        None => DayResults::new(
+            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
+            "<ACTUAL INPUT UNAVAILABLE>".to_string(),
+        ),
+    };
+    input_res.print("=> Actual Input Results:");
+}